@@ -1,14 +1,16 @@
 use criterion::{criterion_group, criterion_main, Criterion};
 use vsss_rust::shamirs_secret_sharing::{generate_shares as sss_generate_shares, reconstruct_secret as sss_reconstruct_secret};
-use vsss_rust::feldman_verifiability::{FeldmanVSSParams, verify_share, reconstruct_secret};
-use num_bigint::ToBigUint;
-use vsss_rust::utils::generate_prime;
+use vsss_rust::feldman_verifiability::{FeldmanVSSParams, verify_share, verify_shares_batch, reconstruct_secret};
+use num_bigint::{BigUint, RandBigInt, ToBigUint};
+use rand::thread_rng;
+use vsss_rust::secret::Secret;
+use vsss_rust::utils::{generate_safe_prime_group, mod_exp, multi_exp, StandardGroup};
 
 fn sss_generation_benchmark(c: &mut Criterion) {
-    let secret = 12345.to_biguint().unwrap();
+    let secret = Secret::new(12345.to_biguint().unwrap());
     let threshold = 3;
     let num_shares = 5;
-    let modulus = 7919.to_biguint().unwrap();
+    let modulus = 65537.to_biguint().unwrap();
 
     c.bench_function("SSS Share Generation", |b| {
         b.iter(|| sss_generate_shares(&secret, threshold, num_shares, &modulus))
@@ -16,11 +18,11 @@ fn sss_generation_benchmark(c: &mut Criterion) {
 }
 
 fn sss_reconstruction_benchmark(c: &mut Criterion) {
-    let secret = 12345.to_biguint().unwrap();
+    let secret = Secret::new(12345.to_biguint().unwrap());
     let threshold = 3;
     let num_shares = 5;
-    let modulus = 7919.to_biguint().unwrap();
-    let shares = sss_generate_shares(&secret, threshold, num_shares, &modulus);
+    let modulus = 65537.to_biguint().unwrap();
+    let shares = sss_generate_shares(&secret, threshold, num_shares, &modulus).unwrap();
 
     c.bench_function("SSS Secret Reconstruction", |b| {
         b.iter(|| sss_reconstruct_secret(&shares[..threshold], &modulus))
@@ -28,12 +30,11 @@ fn sss_reconstruction_benchmark(c: &mut Criterion) {
 }
 
 fn vss_generation_benchmark(c: &mut Criterion) {
-    let secret = 986743267.to_biguint().unwrap();
+    let secret = Secret::new(986743267.to_biguint().unwrap());
     let threshold = 3;
     let num_shares = 5;
-    let g = 2.to_biguint().unwrap();
-    let q = generate_prime(256);
-    let params = FeldmanVSSParams::new(g, q);
+    let group = generate_safe_prime_group(256).unwrap();
+    let params = FeldmanVSSParams::new(group.g, group.p, group.q).unwrap();
 
     c.bench_function("VSS Share Generation", |b| {
         b.iter(|| params.generate_shares(&secret, threshold, num_shares))
@@ -41,44 +42,89 @@ fn vss_generation_benchmark(c: &mut Criterion) {
 }
 
 fn vss_verification_benchmark(c: &mut Criterion) {
-    let secret = 986743267.to_biguint().unwrap();
+    let secret = Secret::new(986743267.to_biguint().unwrap());
     let threshold = 3;
     let num_shares = 5;
-    let g = 2.to_biguint().unwrap();
-    let q = generate_prime(256);
-    let params = FeldmanVSSParams::new(g, q);
-    let (shares, commitments) = params.generate_shares(&secret, threshold, num_shares);
+    let group = generate_safe_prime_group(256).unwrap();
+    let params = FeldmanVSSParams::new(group.g, group.p, group.q).unwrap();
+    let (shares, commitments) = params.generate_shares(&secret, threshold, num_shares).unwrap();
 
     c.bench_function("VSS Share Verification", |b| {
         b.iter(|| {
-            for (i, &(ref x, ref y)) in shares.iter().enumerate() {
-                assert!(verify_share(x, y, &commitments, &params), "Share {} failed verification", i + 1);
+            for (i, share) in shares.iter().enumerate() {
+                assert!(verify_share(share, &commitments, &params), "Share {} failed verification", i + 1);
             }
         })
     });
 }
 
 fn vss_reconstruction_benchmark(c: &mut Criterion) {
-    let secret = 986743267.to_biguint().unwrap();
+    let secret = Secret::new(986743267.to_biguint().unwrap());
     let threshold = 3;
     let num_shares = 5;
-    let g = 2.to_biguint().unwrap();
-    let q = generate_prime(256);
-    let params = FeldmanVSSParams::new(g, q);
-    let (shares, _) = params.generate_shares(&secret, threshold, num_shares);
+    let group = generate_safe_prime_group(256).unwrap();
+    let params = FeldmanVSSParams::new(group.g, group.p, group.q).unwrap();
+    let (shares, _) = params.generate_shares(&secret, threshold, num_shares).unwrap();
 
     c.bench_function("VSS Secret Reconstruction", |b| {
         b.iter(|| reconstruct_secret(&shares[..threshold], &params.q))
     });
 }
 
+fn fixed_base_benchmark(c: &mut Criterion) {
+    let params = FeldmanVSSParams::from_standard_group(StandardGroup::Ffdhe2048);
+    let exponent = thread_rng().gen_biguint_below(&params.q);
+    params.pow_g(&exponent); // Build the table outside the measurement
+
+    c.bench_function("Modular Exponentiation (ffdhe2048)", |b| {
+        b.iter(|| mod_exp(&params.g, &exponent, &params.p))
+    });
+    c.bench_function("Fixed-Base Exponentiation (ffdhe2048)", |b| {
+        b.iter(|| params.pow_g(&exponent))
+    });
+}
+
+fn multi_exp_benchmark(c: &mut Criterion) {
+    let params = FeldmanVSSParams::from_standard_group(StandardGroup::Ffdhe2048);
+    let mut rng = thread_rng();
+    let bases: Vec<BigUint> = (0..8).map(|_| params.pow_g(&rng.gen_biguint_below(&params.q))).collect();
+    let exponents: Vec<BigUint> = (0..8).map(|_| rng.gen_biguint_below(&params.q)).collect();
+
+    c.bench_function("Separate Exponentiations (8 bases)", |b| {
+        b.iter(|| {
+            bases.iter().zip(&exponents).fold(BigUint::from(1u32), |acc, (base, exponent)| {
+                acc * mod_exp(base, exponent, &params.p) % &params.p
+            })
+        })
+    });
+    c.bench_function("Multi-Exponentiation (8 bases)", |b| {
+        b.iter(|| multi_exp(&bases, &exponents, &params.p))
+    });
+}
+
+fn vss_batch_verification_benchmark(c: &mut Criterion) {
+    let secret = Secret::new(986743267.to_biguint().unwrap());
+    let params = FeldmanVSSParams::from_standard_group(StandardGroup::Ffdhe2048);
+    let (shares, commitments) = params.generate_shares(&secret, 5, 100).unwrap();
+
+    c.bench_function("VSS Share Verification (100 shares, ffdhe2048)", |b| {
+        b.iter(|| shares.iter().all(|share| verify_share(share, &commitments, &params)))
+    });
+    c.bench_function("VSS Batch Verification (100 shares, ffdhe2048)", |b| {
+        b.iter(|| assert!(verify_shares_batch(&shares, &commitments, &params).is_empty()))
+    });
+}
+
 criterion_group!(
     vss_benches,
     sss_generation_benchmark,
     sss_reconstruction_benchmark,
     vss_generation_benchmark,
     vss_verification_benchmark,
-    vss_reconstruction_benchmark
+    vss_reconstruction_benchmark,
+    fixed_base_benchmark,
+    multi_exp_benchmark,
+    vss_batch_verification_benchmark
 );
 
 criterion_main!(vss_benches);
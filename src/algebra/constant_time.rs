@@ -0,0 +1,167 @@
+//! A constant-time prime field backend on fixed-width 512-bit integers.
+//!
+//! `num-bigint` arithmetic takes time, and allocates memory, depending on the values it operates
+//! on, which leaks information about secrets and shares through timing. `CtModPField` instead keeps
+//! every element as a 512-bit residue in Montgomery form (`crypto-bigint`'s `DynResidue`), so
+//! additions, multiplications and inversions run in time depending only on the width, never on the
+//! values. Any odd prime modulus of up to 512 bits is supported.
+//!
+//! `CtModPField::new` only checks that the modulus is odd, as testing primality on every call would
+//! cost more than the arithmetic it protects. Over a composite modulus the ring is not a field, and
+//! `inv` returns `None` for every element sharing a factor with the modulus, not only for zero.
+
+use super::PrimeField;
+use crate::error::{Result, VsssError};
+use crypto_bigint::modular::runtime_mod::{DynResidue, DynResidueParams};
+use crypto_bigint::subtle::{Choice, ConstantTimeLess, CtOption};
+use crypto_bigint::{Encoding, NonZero, RandomMod, U512};
+use num_bigint::BigUint;
+use rand::{CryptoRng, RngCore};
+use zeroize::{Zeroize, Zeroizing};
+
+/// The number of limbs of the fixed-width integers.
+const LIMBS: usize = U512::LIMBS;
+
+/// The largest supported modulus size in bits.
+pub const MAX_MODULUS_BITS: u64 = 512;
+
+/// An element of `CtModPField`.
+pub type CtElement = DynResidue<LIMBS>;
+
+/// The integers modulo an odd prime of at most 512 bits, with constant-time arithmetic. The
+/// primality of the modulus is the caller's responsibility.
+#[derive(Clone, Copy, Debug)]
+pub struct CtModPField {
+    params: DynResidueParams<LIMBS>, // Montgomery parameters of the modulus
+    modulus: NonZero<U512>, // The modulus, for sampling random elements
+}
+
+impl CtModPField {
+    /// Creates the field of integers modulo `modulus`.
+    ///
+    /// # Returns
+    ///
+    /// The field, or `VsssError::InvalidGroupParams` if the modulus is even or larger than
+    /// `MAX_MODULUS_BITS` bits.
+    pub fn new(modulus: &BigUint) -> Result<Self> {
+        if modulus.bits() > MAX_MODULUS_BITS || modulus.bits() < 2 || !modulus.bit(0) {
+            return Err(VsssError::InvalidGroupParams);
+        }
+        let modulus = to_uint(modulus);
+        let params = DynResidueParams::new(&modulus);
+        let modulus = Option::from(NonZero::new(modulus)).ok_or(VsssError::InvalidGroupParams)?;
+        Ok(CtModPField { params, modulus })
+    }
+
+    /// Maps an integer below the modulus into the field.
+    ///
+    /// # Returns
+    ///
+    /// The element, or none if `value` is not smaller than the modulus. The comparison with the
+    /// modulus runs in constant time.
+    pub fn from_biguint(&self, value: &BigUint) -> CtOption<CtElement> {
+        let fits = value.bits() <= MAX_MODULUS_BITS;
+        let mut value = if fits { to_uint(value) } else { U512::ZERO };
+        let in_range = Choice::from(u8::from(fits)) & value.ct_lt(&self.modulus);
+        let element = DynResidue::new(&value, self.params);
+        value.zeroize();
+        CtOption::new(element, in_range)
+    }
+
+    /// Returns the canonical integer representative of an element.
+    pub fn to_biguint(&self, element: &CtElement) -> BigUint {
+        let bytes = Zeroizing::new(element.retrieve().to_be_bytes());
+        BigUint::from_bytes_be(bytes.as_ref())
+    }
+}
+
+/// Converts a `BigUint` of at most 512 bits into a fixed-width integer.
+fn to_uint(value: &BigUint) -> U512 {
+    let digits = Zeroizing::new(value.to_bytes_be());
+    let mut bytes = Zeroizing::new([0u8; 64]);
+    bytes[64 - digits.len()..].copy_from_slice(&digits);
+    U512::from_be_slice(bytes.as_ref())
+}
+
+impl PrimeField for CtModPField {
+    type Element = CtElement;
+
+    fn zero(&self) -> CtElement {
+        DynResidue::zero(self.params)
+    }
+
+    fn one(&self) -> CtElement {
+        DynResidue::one(self.params)
+    }
+
+    fn element(&self, value: u64) -> CtElement {
+        DynResidue::new(&U512::from_u64(value), self.params)
+    }
+
+    fn add(&self, a: &CtElement, b: &CtElement) -> CtElement {
+        a + b
+    }
+
+    fn sub(&self, a: &CtElement, b: &CtElement) -> CtElement {
+        a - b
+    }
+
+    fn mul(&self, a: &CtElement, b: &CtElement) -> CtElement {
+        a * b
+    }
+
+    fn inv(&self, a: &CtElement) -> Option<CtElement> {
+        let (inverse, is_some) = a.invert();
+        // Over a prime modulus only zero is not invertible, so the branch reveals nothing about
+        // other values.
+        Option::from(CtOption::new(inverse, is_some.into()))
+    }
+
+    fn random_with_rng<R: RngCore + CryptoRng>(&self, rng: &mut R) -> CtElement {
+        DynResidue::new(&U512::random_mod(rng, &self.modulus), self.params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra::ModPField;
+
+    #[test]
+    fn test_matches_mod_p_field() {
+        // 2^255 - 19
+        let modulus = (BigUint::from(1u32) << 255) - BigUint::from(19u32);
+        let field = CtModPField::new(&modulus).unwrap();
+        let reference = ModPField::new(modulus.clone());
+
+        let a = reference.random();
+        let b = reference.random();
+        let (x, y) = (field.from_biguint(&a).unwrap(), field.from_biguint(&b).unwrap());
+        assert_eq!(field.to_biguint(&field.add(&x, &y)), reference.add(&a, &b));
+        assert_eq!(field.to_biguint(&field.sub(&x, &y)), reference.sub(&a, &b));
+        assert_eq!(field.to_biguint(&field.mul(&x, &y)), reference.mul(&a, &b));
+        assert_eq!(field.to_biguint(&field.inv(&x).unwrap()), reference.inv(&a).unwrap());
+        assert_eq!(field.inv(&field.zero()), None);
+        assert!(field.to_biguint(&field.random()) < modulus);
+    }
+
+    #[test]
+    fn test_rejects_unsupported_moduli() {
+        assert!(CtModPField::new(&BigUint::from(7919u32)).is_ok());
+        assert_eq!(CtModPField::new(&BigUint::from(7918u32)).err(), Some(VsssError::InvalidGroupParams));
+        let too_large = (BigUint::from(1u32) << 521) - BigUint::from(1u32);
+        assert_eq!(CtModPField::new(&too_large).err(), Some(VsssError::InvalidGroupParams));
+        let field = CtModPField::new(&BigUint::from(7919u32)).unwrap();
+        assert!(bool::from(field.from_biguint(&too_large).is_none()));
+        assert!(bool::from(field.from_biguint(&BigUint::from(7919u32)).is_none()));
+        assert!(bool::from(field.from_biguint(&BigUint::from(7918u32)).is_some()));
+    }
+
+    #[test]
+    fn test_composite_modulus() {
+        // 15 is odd but composite: the multiples of 3 and 5 have no inverse.
+        let field = CtModPField::new(&BigUint::from(15u32)).unwrap();
+        assert_eq!(field.inv(&field.element(3)), None);
+        assert_eq!(field.to_biguint(&field.inv(&field.element(2)).unwrap()), BigUint::from(8u32));
+    }
+}
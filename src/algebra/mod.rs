@@ -0,0 +1,233 @@
+//! # Algebraic Abstractions Module
+//!
+//! This module defines the `PrimeField` and `Group` traits the secret sharing schemes are written
+//! against, so that the same Shamir, Feldman and Pedersen code runs over integers modulo a prime,
+//! elliptic curve scalars and points, or any other field and group.
+//!
+//! Both traits are implemented by a context value rather than by the element type itself. This lets
+//! a field or group be chosen at runtime, as with a `BigUint` modulus, while zero-sized types such as
+//! the elliptic curve backends work just as well.
+//!
+//! Groups are written multiplicatively: `mul` is the group operation and `exp` raises an element to
+//! a scalar power. For elliptic curves these are point addition and scalar multiplication.
+//!
+//! With the `constant-time` feature, the `constant_time` module adds `CtModPField`, a prime field
+//! on fixed-width integers whose arithmetic does not depend on the values of its operands.
+
+#[cfg(feature = "constant-time")]
+pub mod constant_time;
+
+use crate::secret::zeroize_biguint;
+use crate::utils::mod_inv;
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::{One, Zero};
+use rand::{thread_rng, CryptoRng, RngCore};
+use std::fmt::Debug;
+
+/// A finite field, used for polynomial coefficients, share indices and share values.
+pub trait PrimeField {
+    /// An element of the field.
+    type Element: Clone + PartialEq + Debug;
+
+    /// Returns the additive identity.
+    fn zero(&self) -> Self::Element;
+    /// Returns the multiplicative identity.
+    fn one(&self) -> Self::Element;
+    /// Maps a small integer, such as a share index, into the field.
+    fn element(&self, value: u64) -> Self::Element;
+    /// Computes `a + b`.
+    fn add(&self, a: &Self::Element, b: &Self::Element) -> Self::Element;
+    /// Computes `a - b`.
+    fn sub(&self, a: &Self::Element, b: &Self::Element) -> Self::Element;
+    /// Computes `a * b`.
+    fn mul(&self, a: &Self::Element, b: &Self::Element) -> Self::Element;
+    /// Computes the multiplicative inverse of `a`, or `None` if `a` is zero.
+    fn inv(&self, a: &Self::Element) -> Option<Self::Element>;
+    /// Samples a uniformly random element of the field using `rng`.
+    fn random_with_rng<R: RngCore + CryptoRng>(&self, rng: &mut R) -> Self::Element;
+
+    /// Samples a uniformly random element of the field using the thread-local RNG.
+    fn random(&self) -> Self::Element {
+        self.random_with_rng(&mut thread_rng())
+    }
+
+    /// Overwrites a secret element with zero. Fields whose elements own heap memory, such as
+    /// `BigUint`, override this to wipe that memory rather than merely drop it.
+    fn zeroize_element(&self, element: &mut Self::Element) {
+        *element = self.zero();
+    }
+}
+
+/// A cyclic group of prime order, used for commitments. Its exponents are elements of `Scalars`.
+pub trait Group {
+    /// The field of exponents, of the same order as the group.
+    type Scalars: PrimeField;
+    /// An element of the group.
+    type Element: Clone + PartialEq + Debug;
+
+    /// Returns the field of exponents.
+    fn scalar_field(&self) -> &Self::Scalars;
+    /// Returns the identity element.
+    fn identity(&self) -> Self::Element;
+    /// Returns the generator.
+    fn generator(&self) -> Self::Element;
+    /// Applies the group operation to `a` and `b`.
+    fn mul(&self, a: &Self::Element, b: &Self::Element) -> Self::Element;
+    /// Raises `a` to the power `k`.
+    fn exp(&self, a: &Self::Element, k: &Scalar<Self>) -> Self::Element;
+}
+
+/// The element type of the field `F`.
+pub type FieldElement<F> = <F as PrimeField>::Element;
+
+/// The element type of the group `G`.
+pub type GroupElement<G> = <G as Group>::Element;
+
+/// The exponent type of the group `G`.
+pub type Scalar<G> = <<G as Group>::Scalars as PrimeField>::Element;
+
+/// The integers modulo a prime `p`, with elements represented as `BigUint`s in `[0, p)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModPField {
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::biguint"))]
+    pub modulus: BigUint, // The prime modulus p
+}
+
+impl ModPField {
+    /// Creates the field of integers modulo `modulus`.
+    pub fn new(modulus: BigUint) -> Self {
+        ModPField { modulus }
+    }
+}
+
+impl PrimeField for ModPField {
+    type Element = BigUint;
+
+    fn zero(&self) -> BigUint {
+        BigUint::zero()
+    }
+
+    fn one(&self) -> BigUint {
+        BigUint::one() % &self.modulus
+    }
+
+    fn element(&self, value: u64) -> BigUint {
+        BigUint::from(value) % &self.modulus
+    }
+
+    fn add(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        (a + b) % &self.modulus
+    }
+
+    fn sub(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        (a + &self.modulus - b % &self.modulus) % &self.modulus
+    }
+
+    fn mul(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        (a * b) % &self.modulus
+    }
+
+    fn inv(&self, a: &BigUint) -> Option<BigUint> {
+        mod_inv(&(a % &self.modulus), &self.modulus).ok()
+    }
+
+    fn random_with_rng<R: RngCore + CryptoRng>(&self, rng: &mut R) -> BigUint {
+        rng.gen_biguint_below(&self.modulus)
+    }
+
+    fn zeroize_element(&self, element: &mut BigUint) {
+        zeroize_biguint(element);
+    }
+}
+
+/// Implements `PrimeField` (the integers modulo `q`) and `Group` (the subgroup of order `q` of
+/// `Z_p^*` generated by `g`) for a parameter struct with `BigUint` fields `g`, `p` and `q`.
+macro_rules! impl_subgroup_mod_p {
+    ($params:ty) => {
+        impl $crate::algebra::PrimeField for $params {
+            type Element = num_bigint::BigUint;
+
+            fn zero(&self) -> num_bigint::BigUint {
+                num_traits::Zero::zero()
+            }
+
+            fn one(&self) -> num_bigint::BigUint {
+                num_traits::One::one()
+            }
+
+            fn element(&self, value: u64) -> num_bigint::BigUint {
+                num_bigint::BigUint::from(value) % &self.q
+            }
+
+            fn add(&self, a: &num_bigint::BigUint, b: &num_bigint::BigUint) -> num_bigint::BigUint {
+                (a + b) % &self.q
+            }
+
+            fn sub(&self, a: &num_bigint::BigUint, b: &num_bigint::BigUint) -> num_bigint::BigUint {
+                (a + &self.q - b % &self.q) % &self.q
+            }
+
+            fn mul(&self, a: &num_bigint::BigUint, b: &num_bigint::BigUint) -> num_bigint::BigUint {
+                (a * b) % &self.q
+            }
+
+            fn inv(&self, a: &num_bigint::BigUint) -> Option<num_bigint::BigUint> {
+                $crate::utils::mod_inv(&(a % &self.q), &self.q).ok()
+            }
+
+            fn random_with_rng<R: rand::RngCore + rand::CryptoRng>(&self, rng: &mut R) -> num_bigint::BigUint {
+                num_bigint::RandBigInt::gen_biguint_below(rng, &self.q)
+            }
+
+            fn zeroize_element(&self, element: &mut num_bigint::BigUint) {
+                $crate::secret::zeroize_biguint(element);
+            }
+        }
+
+        impl $crate::algebra::Group for $params {
+            type Scalars = Self;
+            type Element = num_bigint::BigUint;
+
+            fn scalar_field(&self) -> &Self {
+                self
+            }
+
+            fn identity(&self) -> num_bigint::BigUint {
+                num_traits::One::one()
+            }
+
+            fn generator(&self) -> num_bigint::BigUint {
+                self.g.clone()
+            }
+
+            fn mul(&self, a: &num_bigint::BigUint, b: &num_bigint::BigUint) -> num_bigint::BigUint {
+                (a * b) % &self.p
+            }
+
+            fn exp(&self, a: &num_bigint::BigUint, k: &num_bigint::BigUint) -> num_bigint::BigUint {
+                $crate::utils::mod_exp(a, k, &self.p)
+            }
+        }
+    };
+}
+
+pub(crate) use impl_subgroup_mod_p;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mod_p_field() {
+        let field = ModPField::new(BigUint::from(11u32));
+        let a = field.element(7);
+        let b = field.element(9);
+        assert_eq!(field.add(&a, &b), BigUint::from(5u32));
+        assert_eq!(field.sub(&a, &b), BigUint::from(9u32));
+        assert_eq!(field.mul(&a, &b), BigUint::from(8u32));
+        assert_eq!(field.mul(&a, &field.inv(&a).unwrap()), field.one());
+        assert_eq!(field.inv(&field.zero()), None);
+        assert!(field.random() < field.modulus);
+    }
+}
@@ -0,0 +1,672 @@
+//! # Benaloh Zero-Knowledge Share Validity Proofs
+//!
+//! This module implements Benaloh's cut-and-choose proof that a set of distributed shares
+//! lies on a polynomial of degree less than the threshold, without revealing the polynomial
+//! or any of the shares. Each share is published as a homomorphic commitment
+//! `E_i = g^s_i * h^r_i mod p`, and the dealer privately hands `(s_i, r_i)` to participant `i`.
+//!
+//! The proof runs a number of independent rounds. In every round the dealer commits to the
+//! evaluations of a fresh random masking polynomial `Q` of degree less than the threshold.
+//! Depending on the verifier's challenge bit, the dealer either opens `Q` completely, or opens
+//! the evaluations of `P + Q`, which are uniformly random and therefore reveal nothing about `P`.
+//! A cheating dealer is caught in each round with probability one half, so `k` rounds give a
+//! soundness error of `2^-k` in the interactive proof.
+//!
+//! The non-interactive proof is weaker for the same number of rounds: a cheating dealer can try
+//! fresh round commitments offline until the Fiat-Shamir challenges suit them, which takes about
+//! `2^k` hash evaluations. `verify_non_interactive` therefore rejects transcripts with fewer than
+//! `MIN_NON_INTERACTIVE_ROUNDS` rounds.
+//!
+//! The key functionalities include:
+//! - Commitment of the `Share`s produced by `shamirs_secret_sharing::generate_shares`.
+//! - An interactive prover and verifier exchanging commitments, challenges and responses.
+//! - A non-interactive variant using the Fiat-Shamir heuristic, producing a `BenalohTranscript`
+//!   that anyone can verify against the published share commitments.
+//!
+//! Shares must be generated with the subgroup order `q` as their modulus.
+
+use crate::error::{Result, VsssError};
+use crate::secret::zeroize_biguint;
+use crate::utils::group_params::is_prime_order_subgroup;
+use crate::utils::{derive_generator, gen_rand_with_rng, hash_data, lagrange_interpolation, mod_exp, Polynomial};
+use crate::utils::{dealing_points, Share};
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::Zero;
+use rand::{thread_rng, CryptoRng, Rng, RngCore};
+use zeroize::Zeroize;
+
+/// The minimum number of rounds `verify_non_interactive` accepts, so that finding a transcript
+/// with favourable Fiat-Shamir challenges takes about `2^128` hash evaluations.
+pub const MIN_NON_INTERACTIVE_ROUNDS: usize = 128;
+
+/// Represents the public parameters of the commitment scheme used by the proof.
+///
+/// `g` and `h` must both generate the subgroup of order `q` of `Z_p^*`, and nobody may know
+/// the discrete logarithm of `h` with respect to `g`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BenalohParams {
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::biguint"))]
+    pub p: BigUint, // Modulus of the group Z_p^*
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::biguint"))]
+    pub q: BigUint, // Prime order of the subgroup, also the modulus of the shares
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::biguint"))]
+    pub g: BigUint, // First generator of the subgroup
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::biguint"))]
+    pub h: BigUint, // Second generator of the subgroup with unknown discrete log base g
+}
+
+impl BenalohParams {
+    /// Initializes the parameters from a group modulus, subgroup order and two generators.
+    ///
+    /// The parameters are validated as by `FeldmanVSSParams::new`: `p` and `q` must be prime, `q`
+    /// must divide `p - 1`, and both `g` and `h` must be elements of order exactly `q` modulo `p`.
+    ///
+    /// # Returns
+    ///
+    /// The `BenalohParams` if the parameters are consistent, otherwise `VsssError::InvalidGroupParams`.
+    pub fn new(p: BigUint, q: BigUint, g: BigUint, h: BigUint) -> Result<Self> {
+        let params = BenalohParams { p, q, g, h };
+        if params.is_valid() {
+            Ok(params)
+        } else {
+            Err(VsssError::InvalidGroupParams)
+        }
+    }
+
+    /// Initializes the parameters from a group modulus, subgroup order and a single generator,
+    /// deriving `h` by hashing the other parameters into the subgroup of order `q`.
+    ///
+    /// Because `h` is the output of a hash, nobody (including whoever chose `p`, `q` and `g`)
+    /// knows its discrete logarithm with respect to `g`.
+    ///
+    /// # Returns
+    ///
+    /// The `BenalohParams` if the parameters pass the checks of `new`, otherwise
+    /// `VsssError::InvalidGroupParams`.
+    pub fn from_group(p: BigUint, q: BigUint, g: BigUint) -> Result<Self> {
+        let mut seed = b"benaloh_zk".to_vec();
+        seed.extend_from_slice(&g.to_bytes_be());
+        let h = derive_generator(&p, &q, &seed, 1)?;
+        BenalohParams::new(p, q, g, h)
+    }
+
+    /// Checks that `p` and `q` are prime, that `q` divides `p - 1`, and that `g` and `h` have order
+    /// `q` modulo `p`.
+    pub fn is_valid(&self) -> bool {
+        is_prime_order_subgroup(&self.p, &self.q, &[&self.g, &self.h])
+    }
+
+    /// Computes the commitment `g^value * h^blinding mod p`.
+    pub fn commit(&self, value: &BigUint, blinding: &BigUint) -> BigUint {
+        (mod_exp(&self.g, value, &self.p) * mod_exp(&self.h, blinding, &self.p)) % &self.p
+    }
+
+    /// Commits to every share with a fresh random blinding factor.
+    ///
+    /// # Arguments
+    ///
+    /// * `shares` - The shares, reduced modulo `q`.
+    ///
+    /// # Returns
+    ///
+    /// A tuple of the public commitments and the secret blinding factors. The blinding factor
+    /// at position `i` has to be handed privately to the holder of share `i` together with the share.
+    pub fn commit_shares(&self, shares: &[Share]) -> (Vec<BigUint>, Vec<BigUint>) {
+        self.commit_shares_with_rng(shares, &mut thread_rng())
+    }
+
+    /// Commits to every share as `commit_shares` does, drawing the blinding factors from `rng`.
+    pub fn commit_shares_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        shares: &[Share],
+        rng: &mut R,
+    ) -> (Vec<BigUint>, Vec<BigUint>) {
+        let blindings: Vec<BigUint> = shares.iter().map(|_| gen_rand_with_rng(&self.q, rng)).collect();
+        let commitments = shares.iter().zip(&blindings).map(|(share, r)| {
+            self.commit(share.value.expose_secret(), r)
+        }).collect();
+        (commitments, blindings)
+    }
+
+    /// Checks a single share received from the dealer against its public commitment.
+    pub fn verify_share_commitment(&self, share: &BigUint, blinding: &BigUint, commitment: &BigUint) -> bool {
+        self.commit(share, blinding) == *commitment
+    }
+}
+
+/// The answer of the prover to a single challenge bit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BenalohResponse {
+    /// Answer to challenge `0`: the evaluations of the masking polynomial `Q` and their blindings.
+    Mask {
+        #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::biguint_vec"))]
+        values: Vec<BigUint>,
+        #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::biguint_vec"))]
+        blindings: Vec<BigUint>,
+    },
+    /// Answer to challenge `1`: the evaluations of `P + Q` and the sums of the blindings.
+    Sum {
+        #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::biguint_vec"))]
+        values: Vec<BigUint>,
+        #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::biguint_vec"))]
+        blindings: Vec<BigUint>,
+    },
+}
+
+/// A complete record of a proof: the round commitments, the challenge bits and the responses.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BenalohTranscript {
+    /// For every round, the commitments to the masking polynomial's evaluations at each share index.
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::biguint_vec_vec"))]
+    pub round_commitments: Vec<Vec<BigUint>>,
+    /// The challenge bit of every round.
+    pub challenges: Vec<bool>,
+    /// The prover's response for every round.
+    pub responses: Vec<BenalohResponse>,
+}
+
+/// The dealer's side of the proof.
+///
+/// The masks of the pending round commitments are wiped when they are answered, replaced or
+/// dropped together with the prover.
+pub struct BenalohProver<'a> {
+    params: &'a BenalohParams,
+    shares: &'a [Share],
+    blindings: &'a [BigUint],
+    threshold: usize,
+    masks: Vec<(Vec<BigUint>, Vec<BigUint>)>,
+}
+
+impl<'a> BenalohProver<'a> {
+    /// Creates a prover for the given shares and their commitment blindings. The proof shows that
+    /// the shares lie on a polynomial of degree less than their threshold.
+    ///
+    /// Fails as `dealing_points` does unless the shares come from a single dealing modulo `q` and
+    /// are at least as many as its threshold, and with `VsssError::ShareLengthMismatch` unless there
+    /// is exactly one blinding per share.
+    pub fn new(params: &'a BenalohParams, shares: &'a [Share], blindings: &'a [BigUint]) -> Result<Self> {
+        if blindings.len() != shares.len() {
+            return Err(VsssError::ShareLengthMismatch);
+        }
+        dealing_points(shares, &params.q)?.iter_mut().for_each(|(_, value)| zeroize_biguint(value));
+        let threshold = shares[0].threshold;
+        Ok(BenalohProver { params, shares, blindings, threshold, masks: Vec::new() })
+    }
+
+    /// Produces the prover's first message: for each of `rounds` rounds, commitments to the
+    /// evaluations of a fresh random polynomial of degree less than the threshold.
+    pub fn commit(&mut self, rounds: usize) -> Vec<Vec<BigUint>> {
+        self.commit_with_rng(rounds, &mut thread_rng())
+    }
+
+    /// Produces the prover's first message as `commit` does, drawing the masking polynomials and
+    /// their blindings from `rng`.
+    pub fn commit_with_rng<R: RngCore + CryptoRng>(&mut self, rounds: usize, rng: &mut R) -> Vec<Vec<BigUint>> {
+        self.wipe_masks();
+        let mut round_commitments = Vec::with_capacity(rounds);
+
+        for _ in 0..rounds {
+            let mut mask = random_polynomial(self.threshold, &self.params.q, rng);
+            let values: Vec<BigUint> = self.shares.iter().map(|share| {
+                mask.evaluate(&share.index) % &self.params.q
+            }).collect();
+            mask.zeroize();
+            let blindings: Vec<BigUint> = self.shares.iter().map(|_| gen_rand_with_rng(&self.params.q, rng)).collect();
+            round_commitments.push(values.iter().zip(&blindings).map(|(v, t)| {
+                self.params.commit(v, t)
+            }).collect());
+            self.masks.push((values, blindings));
+        }
+
+        round_commitments
+    }
+
+    /// Answers the verifier's challenge bits, one per committed round.
+    ///
+    /// The masks of the committed rounds are consumed and wiped, so every set of round commitments
+    /// can be answered only once: answering both challenges of a round would reveal the shares.
+    ///
+    /// Fails with `VsssError::NoPendingCommitment` if there are no fresh round commitments to
+    /// answer, and with `VsssError::ChallengeCountMismatch` if the number of challenges differs
+    /// from the number of committed rounds.
+    pub fn respond(&mut self, challenges: &[bool]) -> Result<Vec<BenalohResponse>> {
+        if self.masks.is_empty() {
+            return Err(VsssError::NoPendingCommitment);
+        }
+        if challenges.len() != self.masks.len() {
+            return Err(VsssError::ChallengeCountMismatch { expected: self.masks.len(), provided: challenges.len() });
+        }
+        let q = &self.params.q;
+        let masks = std::mem::take(&mut self.masks);
+
+        Ok(challenges.iter().zip(masks).map(|(&challenge, (mut values, mut blindings))| {
+            if challenge {
+                let sums = values.iter().zip(self.shares).map(|(v, share)| (v + share.value.expose_secret()) % q).collect();
+                let blinding_sums = blindings.iter().zip(self.blindings).map(|(t, r)| (t + r) % q).collect();
+                values.iter_mut().chain(blindings.iter_mut()).for_each(zeroize_biguint);
+                BenalohResponse::Sum { values: sums, blindings: blinding_sums }
+            } else {
+                BenalohResponse::Mask { values, blindings }
+            }
+        }).collect())
+    }
+
+    /// Runs the whole proof non-interactively, deriving the challenges with the Fiat-Shamir heuristic.
+    ///
+    /// # Arguments
+    ///
+    /// * `share_commitments` - The public commitments to the shares, as returned by `commit_shares`.
+    /// * `rounds` - The number of rounds, at least `MIN_NON_INTERACTIVE_ROUNDS` for the transcript
+    ///   to be accepted by `verify_non_interactive`.
+    ///
+    /// Fails with `VsssError::NoPendingCommitment` if `rounds` is zero.
+    pub fn prove_non_interactive(&mut self, share_commitments: &[BigUint], rounds: usize) -> Result<BenalohTranscript> {
+        self.prove_non_interactive_with_rng(share_commitments, rounds, &mut thread_rng())
+    }
+
+    /// Runs the whole proof non-interactively as `prove_non_interactive` does, drawing the round
+    /// commitments from `rng`.
+    pub fn prove_non_interactive_with_rng<R: RngCore + CryptoRng>(
+        &mut self,
+        share_commitments: &[BigUint],
+        rounds: usize,
+        rng: &mut R,
+    ) -> Result<BenalohTranscript> {
+        let round_commitments = self.commit_with_rng(rounds, rng);
+        let indices: Vec<BigUint> = self.shares.iter().map(|share| share.index.clone()).collect();
+        let challenges = fiat_shamir_challenges(
+            self.params, &indices, share_commitments, self.threshold, &round_commitments,
+        );
+        let responses = self.respond(&challenges)?;
+        Ok(BenalohTranscript { round_commitments, challenges, responses })
+    }
+
+    /// Overwrites the masks of the pending round commitments and discards them.
+    fn wipe_masks(&mut self) {
+        for (values, blindings) in self.masks.iter_mut() {
+            values.iter_mut().chain(blindings.iter_mut()).for_each(zeroize_biguint);
+        }
+        self.masks.clear();
+    }
+}
+
+impl Drop for BenalohProver<'_> {
+    fn drop(&mut self) {
+        self.wipe_masks();
+    }
+}
+
+/// The verifier's side of the interactive proof.
+pub struct BenalohVerifier<'a> {
+    params: &'a BenalohParams,
+    indices: &'a [BigUint],
+    share_commitments: &'a [BigUint],
+    threshold: usize,
+    round_commitments: Vec<Vec<BigUint>>,
+    challenges: Vec<bool>,
+}
+
+impl<'a> BenalohVerifier<'a> {
+    /// Creates a verifier for the public share indices, the share commitments and the threshold.
+    pub fn new(
+        params: &'a BenalohParams,
+        indices: &'a [BigUint],
+        share_commitments: &'a [BigUint],
+        threshold: usize,
+    ) -> Self {
+        BenalohVerifier {
+            params,
+            indices,
+            share_commitments,
+            threshold,
+            round_commitments: Vec::new(),
+            challenges: Vec::new(),
+        }
+    }
+
+    /// Records the prover's round commitments and replies with uniformly random challenge bits.
+    pub fn challenge(&mut self, round_commitments: Vec<Vec<BigUint>>) -> Vec<bool> {
+        self.challenge_with_rng(round_commitments, &mut thread_rng())
+    }
+
+    /// Records the prover's round commitments as `challenge` does, drawing the challenge bits from
+    /// `rng`.
+    pub fn challenge_with_rng<R: RngCore + CryptoRng>(
+        &mut self,
+        round_commitments: Vec<Vec<BigUint>>,
+        rng: &mut R,
+    ) -> Vec<bool> {
+        self.challenges = round_commitments.iter().map(|_| rng.gen()).collect();
+        self.round_commitments = round_commitments;
+        self.challenges.clone()
+    }
+
+    /// Checks the prover's responses against the recorded commitments and challenges.
+    pub fn verify(&self, responses: &[BenalohResponse]) -> bool {
+        let transcript = BenalohTranscript {
+            round_commitments: self.round_commitments.clone(),
+            challenges: self.challenges.clone(),
+            responses: responses.to_vec(),
+        };
+        verify_transcript(self.params, self.indices, self.share_commitments, self.threshold, &transcript)
+    }
+}
+
+/// Verifies the responses of a transcript against its own commitments and challenge bits.
+///
+/// This does not check how the challenges were chosen; use `verify_non_interactive` for
+/// transcripts produced by `BenalohProver::prove_non_interactive`.
+///
+/// # Arguments
+///
+/// * `params` - The commitment parameters.
+/// * `indices` - The public share indices (x-coordinates), in the order the shares were committed.
+/// * `share_commitments` - The public commitments to the shares.
+/// * `threshold` - The threshold; the shares must lie on a polynomial of degree less than it.
+/// * `transcript` - The transcript to check.
+///
+/// # Returns
+///
+/// `true` if every round of the transcript is valid, otherwise `false`.
+pub fn verify_transcript(
+    params: &BenalohParams,
+    indices: &[BigUint],
+    share_commitments: &[BigUint],
+    threshold: usize,
+    transcript: &BenalohTranscript,
+) -> bool {
+    let rounds = transcript.round_commitments.len();
+    if rounds == 0 || transcript.challenges.len() != rounds || transcript.responses.len() != rounds {
+        return false;
+    }
+    if indices.len() != share_commitments.len() {
+        return false;
+    }
+
+    transcript.round_commitments.iter()
+        .zip(&transcript.challenges)
+        .zip(&transcript.responses)
+        .all(|((commitments, &challenge), response)| {
+            if commitments.len() != indices.len() {
+                return false;
+            }
+            match (challenge, response) {
+                (false, BenalohResponse::Mask { values, blindings }) => {
+                    opens_commitments(params, commitments, None, values, blindings)
+                        && has_degree_below(indices, values, threshold, &params.q)
+                }
+                (true, BenalohResponse::Sum { values, blindings }) => {
+                    opens_commitments(params, commitments, Some(share_commitments), values, blindings)
+                        && has_degree_below(indices, values, threshold, &params.q)
+                }
+                _ => false,
+            }
+        })
+}
+
+/// Verifies a non-interactive transcript, recomputing the Fiat-Shamir challenges before
+/// checking the responses with `verify_transcript`.
+///
+/// # Returns
+///
+/// `true` if the transcript is valid, otherwise `false`. Transcripts with fewer than
+/// `MIN_NON_INTERACTIVE_ROUNDS` rounds are rejected, as a cheating dealer could find favourable
+/// challenges for them by trial and error.
+pub fn verify_non_interactive(
+    params: &BenalohParams,
+    indices: &[BigUint],
+    share_commitments: &[BigUint],
+    threshold: usize,
+    transcript: &BenalohTranscript,
+) -> bool {
+    if transcript.round_commitments.len() < MIN_NON_INTERACTIVE_ROUNDS {
+        return false;
+    }
+    let expected = fiat_shamir_challenges(
+        params, indices, share_commitments, threshold, &transcript.round_commitments,
+    );
+    expected == transcript.challenges
+        && verify_transcript(params, indices, share_commitments, threshold, transcript)
+}
+
+/// Samples a polynomial of degree less than `threshold` with coefficients uniform in `[0, q)`,
+/// drawn from `rng`.
+fn random_polynomial<R: RngCore + CryptoRng>(threshold: usize, q: &BigUint, rng: &mut R) -> Polynomial {
+    let coefficients = (0..threshold).map(|_| rng.gen_biguint_below(q)).collect();
+    Polynomial { coefficients }
+}
+
+/// Checks that every `values[i]` with `blindings[i]` opens `commitments[i]`, after multiplying
+/// the commitment with the matching share commitment when `share_commitments` is given.
+fn opens_commitments(
+    params: &BenalohParams,
+    commitments: &[BigUint],
+    share_commitments: Option<&[BigUint]>,
+    values: &[BigUint],
+    blindings: &[BigUint],
+) -> bool {
+    if values.len() != commitments.len() || blindings.len() != commitments.len() {
+        return false;
+    }
+
+    commitments.iter().enumerate().all(|(i, commitment)| {
+        if values[i] >= params.q || blindings[i] >= params.q {
+            return false;
+        }
+        let expected = match share_commitments {
+            Some(share_commitments) => (commitment * &share_commitments[i]) % &params.p,
+            None => commitment.clone(),
+        };
+        params.commit(&values[i], &blindings[i]) == expected
+    })
+}
+
+/// Checks that the points `(indices[i], values[i])` lie on a single polynomial of degree
+/// less than `threshold` modulo `q`.
+fn has_degree_below(indices: &[BigUint], values: &[BigUint], threshold: usize, q: &BigUint) -> bool {
+    if indices.len() <= threshold {
+        return true;
+    }
+    let points: Vec<(BigUint, BigUint)> = indices.iter().cloned().zip(values.iter().cloned()).collect();
+    let (base, rest) = points.split_at(threshold);
+
+    rest.iter().all(|(x, y)| {
+        lagrange_interpolation(base, x, q).is_ok_and(|value| value == *y)
+    })
+}
+
+/// Derives one challenge bit per round by hashing the public statement and the round commitments.
+fn fiat_shamir_challenges(
+    params: &BenalohParams,
+    indices: &[BigUint],
+    share_commitments: &[BigUint],
+    threshold: usize,
+    round_commitments: &[Vec<BigUint>],
+) -> Vec<bool> {
+    let mut data = Vec::new();
+    data.extend_from_slice(b"benaloh_zk/challenge");
+    for value in [&params.p, &params.q, &params.g, &params.h] {
+        append_length_prefixed(&mut data, value);
+    }
+    data.extend_from_slice(&(threshold as u64).to_be_bytes());
+    data.extend_from_slice(&(indices.len() as u64).to_be_bytes());
+    for value in indices.iter().chain(share_commitments) {
+        append_length_prefixed(&mut data, value);
+    }
+    data.extend_from_slice(&(round_commitments.len() as u64).to_be_bytes());
+    for value in round_commitments.iter().flatten() {
+        append_length_prefixed(&mut data, value);
+    }
+    let digest = hash_data(&data);
+
+    let mut bits = Vec::with_capacity(round_commitments.len());
+    let mut block: u64 = 0;
+    while bits.len() < round_commitments.len() {
+        let mut input = digest.clone();
+        input.extend_from_slice(&block.to_be_bytes());
+        for byte in hash_data(&input) {
+            for bit in 0..8 {
+                if bits.len() < round_commitments.len() {
+                    bits.push((byte >> bit) & 1 == 1);
+                }
+            }
+        }
+        block += 1;
+    }
+    bits
+}
+
+/// Appends a big integer to `data`, prefixed with its length so encodings cannot collide.
+fn append_length_prefixed(data: &mut Vec<u8>, value: &BigUint) {
+    let bytes = if value.is_zero() { Vec::new() } else { value.to_bytes_be() };
+    data.extend_from_slice(&(bytes.len() as u64).to_be_bytes());
+    data.extend_from_slice(&bytes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secret::Secret;
+    use crate::shamirs_secret_sharing::generate_shares;
+    use num_bigint::ToBigUint;
+    use crate::utils::{generate_safe_prime_group, group_id, new_dealing_id, Scheme};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    fn test_params() -> BenalohParams {
+        let group = generate_safe_prime_group(64).unwrap();
+        BenalohParams::from_group(group.p, group.q, group.g).unwrap()
+    }
+
+    #[test]
+    fn test_rejects_invalid_params() {
+        let params = test_params();
+        let (p, q, g, h) = (params.p.clone(), params.q.clone(), params.g.clone(), params.h.clone());
+        assert!(BenalohParams::new(p.clone(), q.clone(), g.clone(), h.clone()).is_ok());
+
+        // p - 1 has order 2, not q.
+        assert_eq!(
+            BenalohParams::new(p.clone(), q.clone(), g.clone(), &p - 1u32).err(),
+            Some(VsssError::InvalidGroupParams)
+        );
+        // The composite 2q cannot be the order of the subgroup.
+        assert!(BenalohParams::new(p.clone(), &q * 2u32, g.clone(), h).is_err());
+        assert_eq!(BenalohParams::from_group(p, &q * 2u32, g).err(), Some(VsssError::InvalidGroupParams));
+        // No element of Z_2^* can generate a subgroup.
+        assert!(BenalohParams::from_group(2u32.into(), 1u32.into(), 1u32.into()).is_err());
+    }
+
+    #[test]
+    fn test_non_interactive_proof() {
+        let params = test_params();
+        let threshold = 3;
+        let shares = generate_shares(&Secret::new(1234.to_biguint().unwrap()), threshold, 5, &params.q).unwrap();
+        let (commitments, blindings) = params.commit_shares(&shares);
+        let indices: Vec<BigUint> = shares.iter().map(|share| share.index.clone()).collect();
+
+        for (share, (r, c)) in shares.iter().zip(blindings.iter().zip(&commitments)) {
+            assert!(params.verify_share_commitment(share.value.expose_secret(), r, c));
+        }
+
+        let mut prover = BenalohProver::new(&params, &shares, &blindings).unwrap();
+        let transcript = prover.prove_non_interactive(&commitments, MIN_NON_INTERACTIVE_ROUNDS).unwrap();
+        assert!(verify_non_interactive(&params, &indices, &commitments, threshold, &transcript));
+
+        // A short transcript is valid round by round, but too easy to grind.
+        let short = prover.prove_non_interactive(&commitments, 40).unwrap();
+        assert!(verify_transcript(&params, &indices, &commitments, threshold, &short));
+        assert!(!verify_non_interactive(&params, &indices, &commitments, threshold, &short));
+
+        let mut tampered = transcript.clone();
+        tampered.challenges[0] = !tampered.challenges[0];
+        assert!(!verify_non_interactive(&params, &indices, &commitments, threshold, &tampered));
+    }
+
+    #[test]
+    fn test_interactive_proof() {
+        let params = test_params();
+        let threshold = 2;
+        let shares = generate_shares(&Secret::new(42.to_biguint().unwrap()), threshold, 4, &params.q).unwrap();
+        let (commitments, blindings) = params.commit_shares(&shares);
+        let indices: Vec<BigUint> = shares.iter().map(|share| share.index.clone()).collect();
+
+        let mut prover = BenalohProver::new(&params, &shares, &blindings).unwrap();
+        let mut verifier = BenalohVerifier::new(&params, &indices, &commitments, threshold);
+        let challenges = verifier.challenge(prover.commit(20));
+        assert!(verifier.verify(&prover.respond(&challenges).unwrap()));
+
+        let mut mixed = generate_shares(&Secret::new(42.to_biguint().unwrap()), threshold, 4, &params.q).unwrap();
+        mixed[0] = shares[0].clone();
+        assert!(matches!(BenalohProver::new(&params, &mixed, &blindings), Err(VsssError::MixedDealings)));
+        assert!(matches!(BenalohProver::new(&params, &shares[..1], &blindings[..1]), Err(VsssError::InsufficientShares { .. })));
+        assert!(matches!(BenalohProver::new(&params, &shares, &blindings[1..]), Err(VsssError::ShareLengthMismatch)));
+    }
+
+    #[test]
+    fn test_respond_only_once() {
+        let params = test_params();
+        let threshold = 2;
+        let shares = generate_shares(&Secret::new(42.to_biguint().unwrap()), threshold, 4, &params.q).unwrap();
+        let (_, blindings) = params.commit_shares(&shares);
+
+        let mut prover = BenalohProver::new(&params, &shares, &blindings).unwrap();
+        assert_eq!(prover.respond(&[true]), Err(VsssError::NoPendingCommitment));
+        prover.commit(4);
+        assert_eq!(
+            prover.respond(&[true; 3]),
+            Err(VsssError::ChallengeCountMismatch { expected: 4, provided: 3 })
+        );
+        assert!(prover.respond(&[true, false, true, false]).is_ok());
+        // Answering the complementary challenges would reveal the shares.
+        assert_eq!(prover.respond(&[false, true, false, true]), Err(VsssError::NoPendingCommitment));
+    }
+
+    #[test]
+    fn test_seeded_proof() {
+        let params = test_params();
+        let threshold = 2;
+        let shares = generate_shares(&Secret::new(42.to_biguint().unwrap()), threshold, 4, &params.q).unwrap();
+        let (commitments, blindings) = params.commit_shares_with_rng(&shares, &mut ChaCha20Rng::seed_from_u64(7));
+        assert_eq!((commitments.clone(), blindings.clone()), params.commit_shares_with_rng(&shares, &mut ChaCha20Rng::seed_from_u64(7)));
+        let indices: Vec<BigUint> = shares.iter().map(|share| share.index.clone()).collect();
+
+        let mut prover = BenalohProver::new(&params, &shares, &blindings).unwrap();
+        let transcript = prover.prove_non_interactive_with_rng(&commitments, 8, &mut ChaCha20Rng::seed_from_u64(8)).unwrap();
+        assert_eq!(transcript, prover.prove_non_interactive_with_rng(&commitments, 8, &mut ChaCha20Rng::seed_from_u64(8)).unwrap());
+
+        let mut verifier = BenalohVerifier::new(&params, &indices, &commitments, threshold);
+        let challenges = verifier.challenge_with_rng(prover.commit_with_rng(20, &mut ChaCha20Rng::seed_from_u64(9)), &mut ChaCha20Rng::seed_from_u64(10));
+        assert!(verifier.verify(&prover.respond(&challenges).unwrap()));
+        assert_eq!(challenges, verifier.challenge_with_rng(prover.commit(20), &mut ChaCha20Rng::seed_from_u64(10)));
+    }
+
+    #[test]
+    fn test_rejects_shares_of_higher_degree() {
+        let params = test_params();
+        let threshold = 2;
+        // Shares of a degree-2 polynomial presented as if the threshold were 2.
+        let poly = Polynomial {
+            coefficients: vec![42.to_biguint().unwrap(), 7.to_biguint().unwrap(), 1.to_biguint().unwrap()],
+        };
+        let dealing_id = new_dealing_id();
+        let shares: Vec<Share> = (1..=4u32).map(|i| {
+            let x = i.to_biguint().unwrap();
+            let y = Secret::new(poly.evaluate(&x) % &params.q);
+            Share::new(x, y, threshold, group_id(&params.q), dealing_id, Scheme::Shamir)
+        }).collect();
+        let (commitments, blindings) = params.commit_shares(&shares);
+        let indices: Vec<BigUint> = shares.iter().map(|share| share.index.clone()).collect();
+
+        let mut prover = BenalohProver::new(&params, &shares, &blindings).unwrap();
+        let commitments_round = prover.commit(1);
+        let responses = prover.respond(&[true]).unwrap();
+        let transcript = BenalohTranscript {
+            round_commitments: commitments_round,
+            challenges: vec![true],
+            responses,
+        };
+        assert!(!verify_transcript(&params, &indices, &commitments, threshold, &transcript));
+    }
+}
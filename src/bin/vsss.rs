@@ -0,0 +1,456 @@
+//! The `vsss` command-line tool, built with the `cli` feature.
+//!
+//! `vsss split` shares a secret read from standard input, a file or a hexadecimal string with
+//! Shamir's scheme or Feldman's VSS over one of the standard groups, `vsss combine` recovers it
+//! from enough shares, and `vsss verify` checks Feldman shares against the commitments published
+//! by the dealer. Shares are written as Bech32m strings, armored blocks or the hexadecimal wire
+//! format; `combine` and `verify` recognise all three. Secrets too large for the group, such as
+//! whole files, are encrypted with `--encrypt-to` and only the key is shared.
+//!
+//! The exit status tells the failures apart: see `EXIT_VERIFICATION_FAILED`, `EXIT_INVALID_INPUT`
+//! and `EXIT_RECONSTRUCTION_FAILED`. Usage errors exit with status 2.
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use num_bigint::BigUint;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use vsss_rust::error::VsssError;
+use vsss_rust::feldman_verifiability::{self, FeldmanVSSParams};
+use vsss_rust::hybrid::{self, Cipher, EncryptedFile};
+use vsss_rust::secret::Secret;
+use vsss_rust::shamirs_secret_sharing;
+use vsss_rust::utils::armor::ARMOR_BEGIN;
+use vsss_rust::utils::{commitments_from_bytes, commitments_to_bytes, group_id, Scheme, Share, StandardGroup};
+use zeroize::Zeroizing;
+
+/// Exit status when a share fails verification against the commitments.
+const EXIT_VERIFICATION_FAILED: u8 = 1;
+
+/// Exit status when the arguments cannot be used together.
+const EXIT_USAGE: u8 = 2;
+
+/// Exit status when an input cannot be read or decoded, or the secret does not fit the group.
+const EXIT_INVALID_INPUT: u8 = 3;
+
+/// Exit status when the shares do not allow the secret to be reconstructed.
+const EXIT_RECONSTRUCTION_FAILED: u8 = 4;
+
+/// The byte prepended to a secret before it is read as an integer, so that leading zero bytes
+/// survive the round trip.
+const SECRET_MARKER: u8 = 0x01;
+
+#[derive(Parser)]
+#[command(name = "vsss", version, about = "Split, combine and verify secret shares")]
+#[command(after_help = "Exit status: 0 on success, 1 if a share fails verification, 2 on usage errors, \
+3 on unreadable or malformed input, 4 if the secret cannot be reconstructed.")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Split a secret into shares.
+    Split(SplitArgs),
+    /// Reconstruct a secret from shares.
+    Combine(CombineArgs),
+    /// Verify Feldman shares against the dealer's commitments.
+    Verify(VerifyArgs),
+}
+
+#[derive(Args)]
+struct SplitArgs {
+    /// Minimum number of shares required to reconstruct the secret.
+    #[arg(short, long)]
+    threshold: usize,
+    /// Total number of shares to generate.
+    #[arg(short = 'n', long)]
+    shares: usize,
+    /// Sharing scheme.
+    #[arg(long, value_enum, default_value_t = SchemeArg::Shamir)]
+    scheme: SchemeArg,
+    #[command(flatten)]
+    group: GroupArgs,
+    /// Text encoding of the shares [default: bech32, or armor for groups too large for Bech32m,
+    /// from 6144 bits].
+    #[arg(short, long, value_enum)]
+    encoding: Option<Encoding>,
+    /// Secret as a hexadecimal string, instead of reading it from standard input.
+    #[arg(long, conflicts_with = "input")]
+    hex: Option<String>,
+    /// File to read the secret from, instead of standard input.
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+    /// Directory to write one new `share-<index>.txt` file per share to, instead of standard output.
+    #[arg(short, long)]
+    out_dir: Option<PathBuf>,
+    /// File to write the Feldman commitments to.
+    #[arg(short, long, required_if_eq("scheme", "feldman"))]
+    commitments: Option<PathBuf>,
+    /// Encrypt the secret, which may then be of any size, to this file and share only the key.
+    #[arg(long, conflicts_with = "commitments")]
+    encrypt_to: Option<PathBuf>,
+    /// AEAD cipher used with `--encrypt-to`.
+    #[arg(long, value_enum, default_value_t = CipherArg::Aes256Gcm, requires = "encrypt_to")]
+    cipher: CipherArg,
+}
+
+#[derive(Args)]
+struct CombineArgs {
+    #[command(flatten)]
+    group: GroupArgs,
+    /// Print the secret as a hexadecimal string instead of raw bytes.
+    #[arg(long)]
+    hex: bool,
+    /// New file to write the secret to, instead of standard output. Existing files are not overwritten.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+    /// File written by `vsss split --encrypt-to`, to decrypt with the reconstructed key.
+    #[arg(long)]
+    decrypt: Option<PathBuf>,
+    /// Files containing shares; standard input is read if none are given.
+    files: Vec<PathBuf>,
+}
+
+#[derive(Args)]
+struct VerifyArgs {
+    #[command(flatten)]
+    group: GroupArgs,
+    /// File containing the commitments written by `vsss split --scheme feldman`.
+    #[arg(short, long)]
+    commitments: PathBuf,
+    /// Files containing shares; standard input is read if none are given.
+    files: Vec<PathBuf>,
+}
+
+#[derive(Args)]
+struct GroupArgs {
+    /// Standard group whose prime order `q` is the field of the shares.
+    #[arg(short, long, value_enum, default_value_t = GroupArg::Ffdhe2048)]
+    group: GroupArg,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum SchemeArg {
+    Shamir,
+    Feldman,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum CipherArg {
+    Aes256Gcm,
+    Chacha20Poly1305,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Encoding {
+    /// One Bech32m string per share.
+    Bech32,
+    /// One armored block per share.
+    Armor,
+    /// The binary wire format as a hexadecimal string.
+    Hex,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum GroupArg {
+    Modp2048,
+    Modp3072,
+    Modp4096,
+    Modp6144,
+    Modp8192,
+    Ffdhe2048,
+    Ffdhe3072,
+    Ffdhe4096,
+    Ffdhe6144,
+    Ffdhe8192,
+}
+
+impl GroupArgs {
+    fn group(&self) -> StandardGroup {
+        match self.group {
+            GroupArg::Modp2048 => StandardGroup::Modp2048,
+            GroupArg::Modp3072 => StandardGroup::Modp3072,
+            GroupArg::Modp4096 => StandardGroup::Modp4096,
+            GroupArg::Modp6144 => StandardGroup::Modp6144,
+            GroupArg::Modp8192 => StandardGroup::Modp8192,
+            GroupArg::Ffdhe2048 => StandardGroup::Ffdhe2048,
+            GroupArg::Ffdhe3072 => StandardGroup::Ffdhe3072,
+            GroupArg::Ffdhe4096 => StandardGroup::Ffdhe4096,
+            GroupArg::Ffdhe6144 => StandardGroup::Ffdhe6144,
+            GroupArg::Ffdhe8192 => StandardGroup::Ffdhe8192,
+        }
+    }
+}
+
+/// An error of the tool, with the exit status it maps to.
+struct Failure {
+    code: u8, // Exit status
+    message: String, // Message printed to standard error
+}
+
+impl Failure {
+    fn input(message: impl Into<String>) -> Self {
+        Failure { code: EXIT_INVALID_INPUT, message: message.into() }
+    }
+
+    fn split(error: VsssError) -> Self {
+        Failure::input(format!("cannot split the secret: {}", error))
+    }
+
+    fn reconstruction(error: VsssError) -> Self {
+        Failure { code: EXIT_RECONSTRUCTION_FAILED, message: format!("cannot reconstruct the secret: {}", error) }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Split(args) => split(args),
+        Command::Combine(args) => combine(args),
+        Command::Verify(args) => verify(args),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(failure) => {
+            eprintln!("vsss: {}", failure.message);
+            ExitCode::from(failure.code)
+        }
+    }
+}
+
+fn split(args: SplitArgs) -> Result<(), Failure> {
+    let group = args.group.group();
+    let modulus = group.q();
+    let scheme = if args.encrypt_to.is_none() && args.scheme == SchemeArg::Feldman { Scheme::Feldman } else { Scheme::Shamir };
+    let encoding = choose_encoding(args.encoding, scheme, args.threshold, args.shares, &modulus)?;
+    let bytes = match (&args.hex, &args.input) {
+        (Some(hex), _) => Zeroizing::new(hex::decode(hex.trim()).map_err(|e| Failure::input(format!("invalid hexadecimal secret: {}", e)))?),
+        (None, Some(path)) => Zeroizing::new(fs::read(path).map_err(|e| Failure::input(format!("cannot read {}: {}", path.display(), e)))?),
+        (None, None) => {
+            let mut bytes = Zeroizing::new(Vec::new());
+            io::stdin().read_to_end(&mut bytes).map_err(|e| Failure::input(format!("cannot read standard input: {}", e)))?;
+            bytes
+        }
+    };
+
+    let shares = match (&args.encrypt_to, args.scheme) {
+        (Some(path), _) => {
+            let cipher = match args.cipher {
+                CipherArg::Aes256Gcm => Cipher::Aes256Gcm,
+                CipherArg::Chacha20Poly1305 => Cipher::ChaCha20Poly1305,
+            };
+            let (shares, file) = hybrid::split_file(&bytes, cipher, args.threshold, args.shares, &modulus).map_err(Failure::split)?;
+            write_file(path, &file.to_bytes())?;
+            shares
+        }
+        (None, SchemeArg::Shamir) => {
+            shamirs_secret_sharing::generate_shares(&encode_secret(&bytes, &modulus)?, args.threshold, args.shares, &modulus)
+                .map_err(Failure::split)?
+        }
+        (None, SchemeArg::Feldman) => {
+            let secret = encode_secret(&bytes, &modulus)?;
+            let params = FeldmanVSSParams::from_standard_group(group);
+            let (shares, commitments) = params.generate_shares(&secret, args.threshold, args.shares).map_err(Failure::split)?;
+            let encoded = commitments_to_bytes(&commitments, Scheme::Feldman, &params.p).map_err(Failure::split)?;
+            let path = args.commitments.as_deref().expect("required for Feldman shares");
+            write_file(path, format!("{}\n", hex::encode(encoded)).as_bytes())?;
+            shares
+        }
+    };
+
+    let mut stdout = io::stdout().lock();
+    for share in &shares {
+        let text = Zeroizing::new(encode_share(share, encoding, &modulus).map_err(|e| Failure::input(e.to_string()))?);
+        match &args.out_dir {
+            Some(dir) => write_file(&dir.join(format!("share-{}.txt", share.index)), text.as_bytes())?,
+            None => stdout.write_all(text.as_bytes()).map_err(|e| Failure::input(format!("cannot write the shares: {}", e)))?,
+        }
+    }
+    Ok(())
+}
+
+fn combine(args: CombineArgs) -> Result<(), Failure> {
+    let modulus = args.group.group().q();
+    let shares = read_shares(&args.files, &modulus)?;
+    let bytes = match &args.decrypt {
+        Some(path) => {
+            let bytes = fs::read(path).map_err(|e| Failure::input(format!("cannot read {}: {}", path.display(), e)))?;
+            let file = EncryptedFile::from_bytes(&bytes)
+                .map_err(|e| Failure::input(format!("invalid encrypted file {}: {}", path.display(), e)))?;
+            hybrid::combine_file(&shares, &file, &modulus).map_err(Failure::reconstruction)?
+        }
+        None => {
+            let secret = shamirs_secret_sharing::reconstruct_secret(&shares, &modulus).map_err(Failure::reconstruction)?;
+            decode_secret(&secret).map_err(Failure::reconstruction)?
+        }
+    };
+
+    let output = if args.hex { Zeroizing::new(format!("{}\n", hex::encode(&bytes)).into_bytes()) } else { bytes };
+    match &args.output {
+        Some(path) => write_file(path, &output),
+        None => io::stdout().write_all(&output).map_err(|e| Failure::input(format!("cannot write the secret: {}", e))),
+    }
+}
+
+fn verify(args: VerifyArgs) -> Result<(), Failure> {
+    let params = FeldmanVSSParams::from_standard_group(args.group.group());
+    let text = fs::read_to_string(&args.commitments)
+        .map_err(|e| Failure::input(format!("cannot read {}: {}", args.commitments.display(), e)))?;
+    let commitments = hex::decode(text.trim())
+        .map_err(|_| VsssError::InvalidEncoding)
+        .and_then(|bytes| commitments_from_bytes(&bytes, Scheme::Feldman, &params.p))
+        .map_err(|e| Failure::input(format!("invalid commitments in {}: {}", args.commitments.display(), e)))?;
+    let shares = read_shares(&args.files, &params.q)?;
+
+    let mut failed = 0;
+    for share in &shares {
+        let valid = feldman_verifiability::verify_share(share, &commitments, &params);
+        println!("share {}: {}", share.index, if valid { "valid" } else { "INVALID" });
+        failed += usize::from(!valid);
+    }
+    if failed > 0 {
+        return Err(Failure {
+            code: EXIT_VERIFICATION_FAILED,
+            message: format!("{} of {} shares failed verification", failed, shares.len()),
+        });
+    }
+    Ok(())
+}
+
+/// Turns the bytes of a secret into an integer below `modulus`, prepending `SECRET_MARKER` so
+/// that leading zero bytes are kept.
+fn encode_secret(bytes: &[u8], modulus: &BigUint) -> Result<Secret, Failure> {
+    let mut marked = Zeroizing::new(vec![SECRET_MARKER]);
+    marked.extend_from_slice(bytes);
+    let secret = Secret::new(BigUint::from_bytes_be(&marked));
+    if secret.expose_secret() >= modulus {
+        let max = (modulus.bits() as usize - 2) / 8;
+        return Err(Failure::input(format!("the secret is {} bytes long, at most {} fit in the group; use --encrypt-to", bytes.len(), max)));
+    }
+    Ok(secret)
+}
+
+/// Recovers the bytes of a secret encoded by `encode_secret`.
+fn decode_secret(secret: &Secret) -> Result<Zeroizing<Vec<u8>>, VsssError> {
+    let marked = Zeroizing::new(secret.expose_secret().to_bytes_be());
+    match marked.split_first() {
+        Some((&SECRET_MARKER, bytes)) => Ok(Zeroizing::new(bytes.to_vec())),
+        _ => Err(VsssError::InconsistentShares),
+    }
+}
+
+/// Picks the encoding of the shares before anything is written: the requested one, or Bech32m
+/// unless the longest share of the dealing would not fit in a Bech32m string, in which case armor.
+///
+/// Bech32m strings are limited to 1023 characters, which the shares of the groups of 6144 bits
+/// and more exceed; asking for Bech32m with them is a usage error.
+fn choose_encoding(
+    requested: Option<Encoding>,
+    scheme: Scheme,
+    threshold: usize,
+    num_shares: usize,
+    modulus: &BigUint,
+) -> Result<Encoding, Failure> {
+    if let Some(encoding @ (Encoding::Armor | Encoding::Hex)) = requested {
+        return Ok(encoding);
+    }
+    let longest = Share::new(BigUint::from(num_shares), Secret::new(modulus - 1u32), threshold, group_id(modulus), [0xff; 16], scheme);
+    match (requested, longest.to_bech32(modulus).is_ok()) {
+        (_, true) => Ok(Encoding::Bech32),
+        (None, false) => Ok(Encoding::Armor),
+        (Some(_), false) => Err(Failure {
+            code: EXIT_USAGE,
+            message: "the shares of this group are too long for Bech32m; use -e armor or -e hex".to_string(),
+        }),
+    }
+}
+
+/// Encodes a share as text ending with a newline.
+fn encode_share(share: &Share, encoding: Encoding, modulus: &BigUint) -> Result<String, VsssError> {
+    Ok(match encoding {
+        Encoding::Bech32 => format!("{}\n", share.to_bech32(modulus)?),
+        Encoding::Armor => share.to_armor(modulus)?,
+        Encoding::Hex => format!("{}\n", hex::encode(Zeroizing::new(share.to_bytes(modulus)?))),
+    })
+}
+
+/// Parses every share in `text`: armored blocks, or one Bech32m or hexadecimal share per line.
+fn parse_shares(text: &str, modulus: &BigUint) -> Result<Vec<Share>, VsssError> {
+    if text.contains(ARMOR_BEGIN) {
+        return text
+            .split_inclusive(vsss_rust::utils::armor::ARMOR_END)
+            .filter(|block| !block.trim().is_empty())
+            .map(|block| Share::from_armor(block, modulus))
+            .collect();
+    }
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| match hex::decode(line) {
+            Ok(bytes) => Share::from_bytes(&Zeroizing::new(bytes), modulus),
+            Err(_) => Share::from_bech32(line, modulus),
+        })
+        .collect()
+}
+
+/// Reads the shares in `files`, or on standard input if there are none.
+fn read_shares(files: &[PathBuf], modulus: &BigUint) -> Result<Vec<Share>, Failure> {
+    let mut inputs = Vec::new();
+    if files.is_empty() {
+        let mut text = Zeroizing::new(String::new());
+        io::stdin().read_to_string(&mut text).map_err(|e| Failure::input(format!("cannot read standard input: {}", e)))?;
+        inputs.push(("standard input".to_string(), text));
+    }
+    for path in files {
+        let text = fs::read_to_string(path).map_err(|e| Failure::input(format!("cannot read {}: {}", path.display(), e)))?;
+        inputs.push((path.display().to_string(), Zeroizing::new(text)));
+    }
+
+    let mut shares = Vec::new();
+    for (name, text) in &inputs {
+        shares.extend(parse_shares(text, modulus).map_err(|e| Failure::input(format!("invalid share in {}: {}", name, e)))?);
+    }
+    Ok(shares)
+}
+
+/// Writes `contents` to a new file at `path`, readable only by its owner on Unix. An existing file
+/// is never overwritten, so earlier shares or secrets cannot be clobbered by accident.
+fn write_file(path: &Path, contents: &[u8]) -> Result<(), Failure> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(path)
+        .and_then(|mut file| file.write_all(contents))
+        .map_err(|e| Failure::input(format!("cannot write {}: {}", path.display(), e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_encoding() {
+        let modulus = StandardGroup::Ffdhe2048.q();
+        for bytes in [&b""[..], b"\x00\x00key", &[0xff; 255]] {
+            let secret = encode_secret(bytes, &modulus).ok().unwrap();
+            assert_eq!(decode_secret(&secret).unwrap().as_slice(), bytes);
+        }
+        assert_eq!(encode_secret(&[0xff; 256], &modulus).err().unwrap().code, EXIT_INVALID_INPUT);
+    }
+
+    #[test]
+    fn test_parse_shares() {
+        let modulus = StandardGroup::Ffdhe2048.q();
+        let secret = encode_secret(b"secret", &modulus).ok().unwrap();
+        let shares = shamirs_secret_sharing::generate_shares(&secret, 2, 3, &modulus).unwrap();
+        for encoding in [Encoding::Bech32, Encoding::Armor, Encoding::Hex] {
+            let text: String = shares.iter().map(|share| encode_share(share, encoding, &modulus).unwrap()).collect();
+            assert_eq!(parse_shares(&text, &modulus).unwrap(), shares);
+        }
+        assert!(parse_shares("not a share", &modulus).is_err());
+    }
+}
@@ -0,0 +1,324 @@
+//! # Elliptic-Curve Verifiable Secret Sharing Module
+//!
+//! This module implements Feldman's and Pedersen's Verifiable Secret Sharing schemes over
+//! prime-order elliptic curve groups. Polynomial coefficients and shares are curve scalars, and
+//! commitments are curve points, so each commitment is a single compressed point (32 bytes for
+//! Ristretto255, 33 bytes for secp256k1) instead of a multi-kilobyte group element.
+//!
+//! The schemes are written once against the `Curve` trait. Backends are enabled with cargo features:
+//! - `ristretto255`: the Ristretto255 group built on Curve25519, via `curve25519-dalek`.
+//! - `secp256k1`: the secp256k1 curve, via `k256`.
+//!
+//! The key functionalities include:
+//! - Generation of shares and Feldman commitments `a_j * G`.
+//! - Generation of shares, blinding shares and Pedersen commitments `a_j * G + b_j * H`.
+//! - Verification of shares against the public commitments.
+//! - Reconstruction of the secret from a subset of shares using Lagrange interpolation.
+//!
+//! Shares are returned as typed `EcShare`s recording their threshold, dealing and scheme, so shares
+//! of different dealings, or Pedersen blinding shares, are never interpolated together.
+
+#[cfg(feature = "ristretto255")]
+pub mod ristretto255;
+#[cfg(feature = "secp256k1")]
+pub mod secp256k1;
+#[cfg(feature = "serde")]
+pub mod serialization;
+
+use crate::algebra::{Group, GroupElement, PrimeField};
+use crate::error::{Result, VsssError};
+use crate::utils::{new_dealing_id_with_rng, DealingId, Scheme};
+use crate::{feldman_verifiability, pedersen_vss, shamirs_secret_sharing};
+use rand::{thread_rng, CryptoRng, RngCore};
+use std::fmt;
+
+/// A prime-order elliptic curve group. The curve is its own scalar field (`PrimeField`) and
+/// group of points (`Group`), so it plugs directly into the generic schemes.
+pub trait Curve: Group<Scalars = Self> + PrimeField + Default {
+    /// Returns a second generator `H` whose discrete logarithm with respect to `G` is unknown,
+    /// derived by hashing into the group.
+    fn blinding_generator() -> GroupElement<Self>;
+
+    /// Encodes a point in its canonical compressed form.
+    fn point_to_bytes(point: &GroupElement<Self>) -> Vec<u8>;
+    /// Decodes a point from its canonical compressed form, failing with `VsssError::InvalidEncoding`
+    /// if the bytes do not encode a point of the group.
+    fn point_from_bytes(bytes: &[u8]) -> Result<GroupElement<Self>>;
+
+    /// Encodes a scalar in its canonical 32-byte form.
+    fn scalar_to_bytes(scalar: &CurveScalar<Self>) -> Vec<u8>;
+    /// Decodes a scalar from its canonical 32-byte form, failing with `VsssError::InvalidEncoding`
+    /// if the bytes do not encode a reduced scalar.
+    fn scalar_from_bytes(bytes: &[u8]) -> Result<CurveScalar<Self>>;
+}
+
+/// A scalar of the curve `C`.
+pub type CurveScalar<C> = <C as PrimeField>::Element;
+
+/// A point of the curve `C`.
+pub type CurvePoint<C> = <C as Group>::Element;
+
+/// A share of a secret over the curve `C`, together with the metadata identifying its dealing.
+///
+/// The value is overwritten when the share is dropped and redacted when the share is formatted;
+/// the index and the metadata are public.
+pub struct EcShare<C: Curve> {
+    pub index: CurveScalar<C>, // x-coordinate, the point the polynomial was evaluated at
+    pub value: CurveScalar<C>, // y-coordinate, the value of the polynomial at the index
+    pub threshold: usize, // Minimum number of shares required to reconstruct the secret
+    pub dealing_id: DealingId, // Identifier of the dealing the share belongs to
+    pub scheme: Scheme, // Scheme the share was produced by
+}
+
+impl<C: Curve> EcShare<C> {
+    /// Creates a share from its point and the metadata of its dealing.
+    pub fn new(index: CurveScalar<C>, value: CurveScalar<C>, threshold: usize, dealing_id: DealingId, scheme: Scheme) -> Self {
+        EcShare { index, value, threshold, dealing_id, scheme }
+    }
+}
+
+impl<C: Curve> Clone for EcShare<C> {
+    fn clone(&self) -> Self {
+        EcShare::new(self.index.clone(), self.value.clone(), self.threshold, self.dealing_id, self.scheme)
+    }
+}
+
+impl<C: Curve> PartialEq for EcShare<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+            && self.value == other.value
+            && self.threshold == other.threshold
+            && self.dealing_id == other.dealing_id
+            && self.scheme == other.scheme
+    }
+}
+
+impl<C: Curve> fmt::Debug for EcShare<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EcShare")
+            .field("index", &self.index)
+            .field("value", &"<redacted>")
+            .field("threshold", &self.threshold)
+            .field("dealing_id", &self.dealing_id)
+            .field("scheme", &self.scheme)
+            .finish()
+    }
+}
+
+impl<C: Curve> Drop for EcShare<C> {
+    fn drop(&mut self) {
+        C::default().zeroize_element(&mut self.value);
+    }
+}
+
+/// Generates shares for Feldman's VSS over the curve `C`, together with the commitments to the
+/// coefficients of the sharing polynomial.
+///
+/// # Arguments
+///
+/// * `secret` - The scalar secret to be shared.
+/// * `threshold` - The minimum number of shares required to reconstruct the secret.
+/// * `num_shares` - The total number of shares to be generated.
+///
+/// # Returns
+///
+/// A tuple containing the shares, tagged with a fresh dealing identifier, and the commitments
+/// `a_j * G`, or `VsssError::InvalidThreshold` if the threshold is zero or exceeds `num_shares`.
+#[allow(clippy::type_complexity)]
+pub fn generate_shares<C: Curve>(
+    secret: &CurveScalar<C>,
+    threshold: usize,
+    num_shares: usize,
+) -> Result<(Vec<EcShare<C>>, Vec<CurvePoint<C>>)> {
+    generate_shares_with_rng::<C, _>(secret, threshold, num_shares, &mut thread_rng())
+}
+
+/// Generates Feldman shares over the curve `C` as `generate_shares` does, drawing the polynomial
+/// coefficients and the dealing identifier from `rng`.
+#[allow(clippy::type_complexity)]
+pub fn generate_shares_with_rng<C: Curve, R: RngCore + CryptoRng>(
+    secret: &CurveScalar<C>,
+    threshold: usize,
+    num_shares: usize,
+    rng: &mut R,
+) -> Result<(Vec<EcShare<C>>, Vec<CurvePoint<C>>)> {
+    let (points, commitments) = feldman_verifiability::generate_shares_over_with_rng(&C::default(), secret, threshold, num_shares, rng)?;
+    let dealing_id = new_dealing_id_with_rng(rng);
+
+    let shares = points.into_iter().map(|(x, y)| {
+        EcShare::new(x, y, threshold, dealing_id, Scheme::Feldman)
+    }).collect();
+
+    Ok((shares, commitments))
+}
+
+/// Verifies a share against Feldman commitments by checking `share * G == sum(i^j * C_j)`.
+///
+/// # Arguments
+///
+/// * `share` - The `EcShare` being verified.
+/// * `commitments` - The public commitments to the polynomial coefficients.
+///
+/// # Returns
+///
+/// `true` if the share is valid, otherwise `false`. The share must be a Feldman share with a
+/// threshold matching the number of commitments.
+pub fn verify_share<C: Curve>(share: &EcShare<C>, commitments: &[CurvePoint<C>]) -> bool {
+    share.scheme == Scheme::Feldman
+        && share.threshold == commitments.len()
+        && feldman_verifiability::verify_share_over(&C::default(), &share.index, &share.value, commitments)
+}
+
+/// Generates shares for Pedersen's VSS over the curve `C`, together with blinding shares and the
+/// commitments `a_j * G + b_j * H` to the coefficients of both polynomials.
+///
+/// # Returns
+///
+/// A tuple containing one `(share, blinding_share)` pair per participant, both tagged with the same
+/// dealing identifier, and the commitments, or `VsssError::InvalidThreshold` if the threshold is
+/// zero or exceeds `num_shares`.
+#[allow(clippy::type_complexity)]
+pub fn generate_pedersen_shares<C: Curve>(
+    secret: &CurveScalar<C>,
+    threshold: usize,
+    num_shares: usize,
+) -> Result<(Vec<(EcShare<C>, EcShare<C>)>, Vec<CurvePoint<C>>)> {
+    generate_pedersen_shares_with_rng::<C, _>(secret, threshold, num_shares, &mut thread_rng())
+}
+
+/// Generates Pedersen shares over the curve `C` as `generate_pedersen_shares` does, drawing both
+/// polynomials and the dealing identifier from `rng`.
+#[allow(clippy::type_complexity)]
+pub fn generate_pedersen_shares_with_rng<C: Curve, R: RngCore + CryptoRng>(
+    secret: &CurveScalar<C>,
+    threshold: usize,
+    num_shares: usize,
+    rng: &mut R,
+) -> Result<(Vec<(EcShare<C>, EcShare<C>)>, Vec<CurvePoint<C>>)> {
+    let (points, commitments) = pedersen_vss::generate_shares_over_with_rng(&C::default(), &C::blinding_generator(), secret, threshold, num_shares, rng)?;
+    let dealing_id = new_dealing_id_with_rng(rng);
+
+    let shares = points.into_iter().map(|((x, y), (_, z))| {
+        let blinding_share = EcShare::new(x.clone(), z, threshold, dealing_id, Scheme::PedersenBlinding);
+        (EcShare::new(x, y, threshold, dealing_id, Scheme::Pedersen), blinding_share)
+    }).collect();
+
+    Ok((shares, commitments))
+}
+
+/// Verifies a share and its blinding share against Pedersen commitments by checking
+/// `share * G + blinding_share * H == sum(i^j * C_j)`.
+///
+/// # Returns
+///
+/// `true` if the share is valid, otherwise `false`. The share and blinding share must be a Pedersen
+/// share and blinding share of the same dealing and index, and both have a threshold matching the
+/// number of commitments.
+pub fn verify_pedersen_share<C: Curve>(
+    share: &EcShare<C>,
+    blinding_share: &EcShare<C>,
+    commitments: &[CurvePoint<C>],
+) -> bool {
+    share.scheme == Scheme::Pedersen
+        && blinding_share.scheme == Scheme::PedersenBlinding
+        && share.threshold == commitments.len()
+        && blinding_share.threshold == share.threshold
+        && blinding_share.dealing_id == share.dealing_id
+        && blinding_share.index == share.index
+        && pedersen_vss::verify_share_over(&C::default(), &C::blinding_generator(), &share.index, &share.value, &blinding_share.value, commitments)
+}
+
+/// Reconstructs the secret from a set of shares using Lagrange interpolation at zero over the
+/// scalar field of `C`.
+///
+/// # Arguments
+///
+/// * `shares` - A slice of shares from a single dealing, at least as many as its threshold.
+///
+/// # Returns
+///
+/// The reconstructed secret. Fails with `VsssError::MixedDealings` if the shares disagree on their
+/// dealing, scheme or threshold, `VsssError::InsufficientShares` if there are fewer shares than the
+/// threshold, and `VsssError::DuplicateShareIndex` if two shares have the same index.
+pub fn reconstruct_secret<C: Curve>(shares: &[EcShare<C>]) -> Result<CurveScalar<C>> {
+    let first = shares.first().ok_or(VsssError::InsufficientShares { required: 1, provided: 0 })?;
+    if shares.iter().any(|share| {
+        share.dealing_id != first.dealing_id || share.threshold != first.threshold || share.scheme != first.scheme
+    }) {
+        return Err(VsssError::MixedDealings);
+    }
+    if shares.len() < first.threshold {
+        return Err(VsssError::InsufficientShares { required: first.threshold, provided: shares.len() });
+    }
+
+    let field = C::default();
+    let mut points: Vec<_> = shares.iter().map(|share| (share.index.clone(), share.value.clone())).collect();
+    let secret = shamirs_secret_sharing::reconstruct_secret_over(&field, &points);
+    points.iter_mut().for_each(|(_, value)| field.zeroize_element(value));
+    secret
+}
+
+#[cfg(all(test, any(feature = "ristretto255", feature = "secp256k1")))]
+mod tests {
+    use super::*;
+
+    /// Runs the Feldman and Pedersen round trips against any backend.
+    fn check_curve<C: Curve>() {
+        let field = C::default();
+        let secret = field.element(986743267);
+        let threshold = 3;
+
+        let (shares, commitments) = generate_shares::<C>(&secret, threshold, 5).unwrap();
+        for share in &shares {
+            assert!(verify_share::<C>(share, &commitments));
+        }
+        let mut tampered = shares[0].clone();
+        tampered.value = field.add(&tampered.value, &field.one());
+        assert!(!verify_share::<C>(&tampered, &commitments));
+        assert_eq!(reconstruct_secret::<C>(&shares[2..]), Ok(secret.clone()));
+        assert_eq!(
+            reconstruct_secret::<C>(&shares[3..]),
+            Err(VsssError::InsufficientShares { required: threshold, provided: 2 })
+        );
+
+        for commitment in &commitments {
+            let bytes = C::point_to_bytes(commitment);
+            assert_eq!(C::point_from_bytes(&bytes), Ok(commitment.clone()));
+        }
+        for share in &shares {
+            let bytes = C::scalar_to_bytes(&share.value);
+            assert_eq!(bytes.len(), 32);
+            assert_eq!(C::scalar_from_bytes(&bytes), Ok(share.value.clone()));
+        }
+        assert_eq!(C::scalar_from_bytes(&[0xff; 32]), Err(VsssError::InvalidEncoding));
+
+        let (pedersen_shares, commitments) = generate_pedersen_shares::<C>(&secret, threshold, 5).unwrap();
+        for (share, blinding_share) in &pedersen_shares {
+            assert!(verify_pedersen_share::<C>(share, blinding_share, &commitments));
+        }
+        let (share, blinding_share) = &pedersen_shares[1];
+        let mut tampered = blinding_share.clone();
+        tampered.value = field.add(&tampered.value, &field.one());
+        assert!(!verify_pedersen_share::<C>(share, &tampered, &commitments));
+        assert!(!verify_pedersen_share::<C>(share, &pedersen_shares[2].1, &commitments));
+        let secret_shares: Vec<_> = pedersen_shares.iter().map(|(share, _)| share.clone()).collect();
+        assert_eq!(reconstruct_secret::<C>(&secret_shares[..threshold]), Ok(secret));
+
+        // Shares of different dealings are not interpolated together.
+        let mixed = vec![shares[0].clone(), shares[1].clone(), secret_shares[2].clone()];
+        assert_eq!(reconstruct_secret::<C>(&mixed), Err(VsssError::MixedDealings));
+        assert!(!format!("{:?}", shares[0]).contains(&format!("{:?}", shares[0].value)));
+    }
+
+    #[cfg(feature = "ristretto255")]
+    #[test]
+    fn test_ristretto255() {
+        check_curve::<crate::ec_vss::ristretto255::Ristretto255>();
+    }
+
+    #[cfg(feature = "secp256k1")]
+    #[test]
+    fn test_secp256k1() {
+        check_curve::<crate::ec_vss::secp256k1::Secp256k1>();
+    }
+}
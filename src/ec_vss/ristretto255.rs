@@ -0,0 +1,110 @@
+//! The Ristretto255 backend, a prime-order group built on Curve25519.
+
+use super::Curve;
+use crate::algebra::{Group, PrimeField};
+use crate::error::{Result, VsssError};
+use crate::utils::hash_data;
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::Identity;
+use rand::{CryptoRng, RngCore};
+
+/// The Ristretto255 group. Commitments encode to 32 bytes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Ristretto255;
+
+impl PrimeField for Ristretto255 {
+    type Element = Scalar;
+
+    fn zero(&self) -> Scalar {
+        Scalar::ZERO
+    }
+
+    fn one(&self) -> Scalar {
+        Scalar::ONE
+    }
+
+    fn element(&self, value: u64) -> Scalar {
+        Scalar::from(value)
+    }
+
+    fn add(&self, a: &Scalar, b: &Scalar) -> Scalar {
+        a + b
+    }
+
+    fn sub(&self, a: &Scalar, b: &Scalar) -> Scalar {
+        a - b
+    }
+
+    fn mul(&self, a: &Scalar, b: &Scalar) -> Scalar {
+        a * b
+    }
+
+    fn inv(&self, a: &Scalar) -> Option<Scalar> {
+        if *a == Scalar::ZERO {
+            None
+        } else {
+            Some(a.invert())
+        }
+    }
+
+    fn random_with_rng<R: RngCore + CryptoRng>(&self, rng: &mut R) -> Scalar {
+        Scalar::random(rng)
+    }
+}
+
+impl Group for Ristretto255 {
+    type Scalars = Self;
+    type Element = RistrettoPoint;
+
+    fn scalar_field(&self) -> &Self {
+        self
+    }
+
+    fn identity(&self) -> RistrettoPoint {
+        RistrettoPoint::identity()
+    }
+
+    fn generator(&self) -> RistrettoPoint {
+        RISTRETTO_BASEPOINT_POINT
+    }
+
+    fn mul(&self, a: &RistrettoPoint, b: &RistrettoPoint) -> RistrettoPoint {
+        a + b
+    }
+
+    fn exp(&self, a: &RistrettoPoint, k: &Scalar) -> RistrettoPoint {
+        a * k
+    }
+}
+
+impl Curve for Ristretto255 {
+    fn blinding_generator() -> RistrettoPoint {
+        // Map 64 uniform bytes to the group; the map has no known discrete logarithm trapdoor.
+        let mut uniform = [0u8; 64];
+        uniform[..32].copy_from_slice(&hash_data(b"vsss-rust/ristretto255/H/0"));
+        uniform[32..].copy_from_slice(&hash_data(b"vsss-rust/ristretto255/H/1"));
+        RistrettoPoint::from_uniform_bytes(&uniform)
+    }
+
+    fn point_to_bytes(point: &RistrettoPoint) -> Vec<u8> {
+        point.compress().to_bytes().to_vec()
+    }
+
+    fn point_from_bytes(bytes: &[u8]) -> Result<RistrettoPoint> {
+        CompressedRistretto::from_slice(bytes)
+            .ok()
+            .and_then(|compressed| compressed.decompress())
+            .ok_or(VsssError::InvalidEncoding)
+    }
+
+    fn scalar_to_bytes(scalar: &Scalar) -> Vec<u8> {
+        scalar.to_bytes().to_vec()
+    }
+
+    fn scalar_from_bytes(bytes: &[u8]) -> Result<Scalar> {
+        let bytes: [u8; 32] = bytes.try_into().map_err(|_| VsssError::InvalidEncoding)?;
+        Option::from(Scalar::from_canonical_bytes(bytes)).ok_or(VsssError::InvalidEncoding)
+    }
+}
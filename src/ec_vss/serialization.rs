@@ -0,0 +1,176 @@
+//! Serde support for elliptic-curve shares and commitments, enabled with the `serde` feature.
+//!
+//! Scalars are encoded in their canonical 32-byte form and points in their canonical compressed
+//! form (32 bytes for Ristretto255, 33 bytes for secp256k1), both as hexadecimal strings like the
+//! other byte strings of the crate. Decoding rejects unreduced scalars and bytes that do not encode
+//! a point of the group.
+//!
+//! `EcShare` implements `Serialize` and `Deserialize` directly. Bare scalars and points are plain
+//! backend types that do not name their curve, so the modules below take the curve as a type
+//! parameter and are used with `serialize_with` and `deserialize_with`:
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Dealing {
+//!     #[serde(
+//!         serialize_with = "vsss_rust::ec_vss::serialization::point_vec::serialize::<Ristretto255, _>",
+//!         deserialize_with = "vsss_rust::ec_vss::serialization::point_vec::deserialize::<Ristretto255, _>"
+//!     )]
+//!     commitments: Vec<RistrettoPoint>,
+//!     shares: Vec<EcShare<Ristretto255>>,
+//! }
+//! ```
+
+use super::{Curve, CurvePoint, CurveScalar, EcShare};
+use crate::utils::serialization::bytes;
+use crate::utils::{DealingId, Scheme};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use zeroize::Zeroizing;
+
+/// (De)serializes a scalar of the curve `C` as a hexadecimal string.
+pub mod scalar {
+    use super::*;
+
+    pub fn serialize<C: Curve, S: Serializer>(scalar: &CurveScalar<C>, serializer: S) -> Result<S::Ok, S::Error> {
+        bytes::serialize(&Zeroizing::new(C::scalar_to_bytes(scalar)), serializer)
+    }
+
+    pub fn deserialize<'de, C: Curve, D: Deserializer<'de>>(deserializer: D) -> Result<CurveScalar<C>, D::Error> {
+        let encoded: Zeroizing<Vec<u8>> = bytes::deserialize(deserializer)?;
+        C::scalar_from_bytes(&encoded).map_err(D::Error::custom)
+    }
+}
+
+/// (De)serializes a point of the curve `C` as a hexadecimal string.
+pub mod point {
+    use super::*;
+
+    pub fn serialize<C: Curve, S: Serializer>(point: &CurvePoint<C>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(C::point_to_bytes(point)))
+    }
+
+    pub fn deserialize<'de, C: Curve, D: Deserializer<'de>>(deserializer: D) -> Result<CurvePoint<C>, D::Error> {
+        let encoded: Vec<u8> = bytes::deserialize(deserializer)?;
+        C::point_from_bytes(&encoded).map_err(D::Error::custom)
+    }
+}
+
+/// (De)serializes a vector of points of the curve `C`, such as commitments, as a list of
+/// hexadecimal strings.
+pub mod point_vec {
+    use super::*;
+
+    pub fn serialize<C: Curve, S: Serializer>(points: &[CurvePoint<C>], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(points.iter().map(|point| hex::encode(C::point_to_bytes(point))))
+    }
+
+    pub fn deserialize<'de, C: Curve, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<CurvePoint<C>>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|encoded| {
+                let encoded = hex::decode(encoded).map_err(D::Error::custom)?;
+                C::point_from_bytes(&encoded).map_err(D::Error::custom)
+            })
+            .collect()
+    }
+}
+
+/// The encoded form of an `EcShare`, with the same fields in the same order.
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "EcShare")]
+struct EncodedShare {
+    #[serde(with = "bytes")]
+    index: Vec<u8>,
+    #[serde(with = "bytes")]
+    value: Zeroizing<Vec<u8>>,
+    threshold: usize,
+    #[serde(with = "bytes")]
+    dealing_id: DealingId,
+    scheme: Scheme,
+}
+
+impl<C: Curve> Serialize for EcShare<C> {
+    /// Serializes the share with its index and value as hexadecimal scalars. Whoever stores or
+    /// transmits the encoding is responsible for protecting the value.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        EncodedShare {
+            index: C::scalar_to_bytes(&self.index),
+            value: Zeroizing::new(C::scalar_to_bytes(&self.value)),
+            threshold: self.threshold,
+            dealing_id: self.dealing_id,
+            scheme: self.scheme,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, C: Curve> Deserialize<'de> for EcShare<C> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let encoded = EncodedShare::deserialize(deserializer)?;
+        Ok(EcShare::new(
+            C::scalar_from_bytes(&encoded.index).map_err(D::Error::custom)?,
+            C::scalar_from_bytes(&encoded.value).map_err(D::Error::custom)?,
+            encoded.threshold,
+            encoded.dealing_id,
+            encoded.scheme,
+        ))
+    }
+}
+
+#[cfg(all(test, any(feature = "ristretto255", feature = "secp256k1")))]
+mod tests {
+    use super::*;
+    use crate::ec_vss::{generate_pedersen_shares, generate_shares, reconstruct_secret, verify_pedersen_share, verify_share};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(bound = "")]
+    struct Dealing<C: Curve> {
+        #[serde(serialize_with = "point_vec::serialize::<C, _>", deserialize_with = "point_vec::deserialize::<C, _>")]
+        commitments: Vec<CurvePoint<C>>,
+        shares: Vec<EcShare<C>>,
+    }
+
+    /// Round-trips a Feldman dealing and a Pedersen share over `C` through JSON.
+    fn check_curve<C: Curve>(point_len: usize) {
+        let secret = C::default().element(42);
+        let (shares, commitments) = generate_shares::<C>(&secret, 2, 3).unwrap();
+        let json = serde_json::to_string(&shares[0]).unwrap();
+        assert!(json.starts_with(r#"{"index":""#));
+        assert!(json.ends_with(r#","scheme":"Feldman"}"#));
+        assert_eq!(serde_json::from_str::<EcShare<C>>(&json).unwrap(), shares[0]);
+
+        let json = serde_json::to_string(&Dealing::<C> { commitments, shares }).unwrap();
+        let encoded: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(encoded["commitments"][0].as_str().unwrap().len(), 2 * point_len);
+        let decoded: Dealing<C> = serde_json::from_str(&json).unwrap();
+        assert!(decoded.shares.iter().all(|share| verify_share::<C>(share, &decoded.commitments)));
+        assert_eq!(reconstruct_secret::<C>(&decoded.shares), Ok(secret.clone()));
+
+        let (pedersen_shares, commitments) = generate_pedersen_shares::<C>(&secret, 2, 3).unwrap();
+        let json = serde_json::to_string(&pedersen_shares[1]).unwrap();
+        let (share, blinding_share): (EcShare<C>, EcShare<C>) = serde_json::from_str(&json).unwrap();
+        assert_eq!(blinding_share.scheme, Scheme::PedersenBlinding);
+        assert!(verify_pedersen_share::<C>(&share, &blinding_share, &commitments));
+
+        // Truncated scalars, unreduced scalars and bytes off the curve are rejected.
+        let json = serde_json::to_string(&decoded.shares[1]).unwrap();
+        let index = hex::encode(C::scalar_to_bytes(&decoded.shares[1].index));
+        assert!(serde_json::from_str::<EcShare<C>>(&json.replace(&index, &index[2..])).is_err());
+        assert!(serde_json::from_str::<EcShare<C>>(&json.replace(&index, &"ff".repeat(32))).is_err());
+        let json = format!(r#"{{"commitments":["{}"],"shares":[]}}"#, "ff".repeat(point_len));
+        assert!(serde_json::from_str::<Dealing<C>>(&json).is_err());
+    }
+
+    #[cfg(feature = "ristretto255")]
+    #[test]
+    fn test_ristretto255_encoding() {
+        check_curve::<crate::ec_vss::ristretto255::Ristretto255>(32);
+    }
+
+    #[cfg(feature = "secp256k1")]
+    #[test]
+    fn test_secp256k1_encoding() {
+        check_curve::<crate::ec_vss::secp256k1::Secp256k1>(33);
+    }
+}
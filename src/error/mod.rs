@@ -0,0 +1,133 @@
+//! # Error Module
+//!
+//! This module defines `VsssError`, the error type returned by every fallible function of the
+//! crate, and the matching `Result` alias. Each variant names one way in which share generation,
+//! reconstruction or parameter setup can go wrong, so callers can react to it precisely instead of
+//! receiving a bare `None` or a panic.
+
+use num_bigint::BigUint;
+use num_traits::ToPrimitive;
+use std::fmt;
+
+/// The errors that can occur while generating, verifying or reconstructing shares.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VsssError {
+    /// The threshold is zero or larger than the number of shares.
+    InvalidThreshold { threshold: usize, num_shares: usize },
+    /// More shares were requested than the field has distinct non-zero indices for.
+    TooManyShares { num_shares: usize, max: usize },
+    /// Two of the given shares have the same index.
+    DuplicateShareIndex,
+    /// A share index is not usable, for example zero, which would reveal the secret.
+    InvalidShareIndex,
+    /// Fewer shares were given than are needed to reconstruct the secret.
+    InsufficientShares { required: usize, provided: usize },
+    /// The given shares belong to different dealings or disagree on the threshold.
+    MixedDealings,
+    /// A share was produced for a different modulus or group than the one it is used with.
+    GroupMismatch,
+    /// The given shares do not all have the same length, or the values accompanying them, such as
+    /// commitment blindings, are not one per share.
+    ShareLengthMismatch,
+    /// The secret is not smaller than the modulus, so it cannot be shared without being reduced.
+    SecretOutOfRange,
+    /// A value has no multiplicative inverse modulo the modulus.
+    NonInvertible,
+    /// The group parameters are inconsistent, for example `g` does not have order `q` modulo `p`.
+    InvalidGroupParams,
+    /// A byte string is not the encoding of a valid element.
+    InvalidEncoding,
+    /// A text encoding of a share fails its checksum, for example because of a typo. `position` is
+    /// the 1-based character of a Bech32m string, line of an armored block or word of a mnemonic
+    /// that is most likely wrong, when it can be determined.
+    CorruptedText { position: Option<usize> },
+    /// The shares are not consistent with a single secret, for example because one of them was
+    /// altered.
+    InconsistentShares,
+    /// A ciphertext failed authentication: it was tampered with, or the key is wrong.
+    AuthenticationFailed,
+    /// A proof response was requested without fresh round commitments to answer, for example
+    /// because the pending commitments were already answered.
+    NoPendingCommitment,
+    /// The number of challenges does not match the number of committed proof rounds.
+    ChallengeCountMismatch { expected: usize, provided: usize },
+}
+
+impl fmt::Display for VsssError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VsssError::InvalidThreshold { threshold, num_shares } => {
+                write!(f, "invalid threshold {} for {} shares", threshold, num_shares)
+            }
+            VsssError::TooManyShares { num_shares, max } => {
+                write!(f, "cannot generate {} shares, at most {} are supported", num_shares, max)
+            }
+            VsssError::DuplicateShareIndex => write!(f, "two shares have the same index"),
+            VsssError::InvalidShareIndex => write!(f, "invalid share index"),
+            VsssError::InsufficientShares { required, provided } => {
+                write!(f, "{} shares are required but only {} were provided", required, provided)
+            }
+            VsssError::MixedDealings => write!(f, "shares belong to different dealings"),
+            VsssError::GroupMismatch => write!(f, "share was produced for a different group"),
+            VsssError::ShareLengthMismatch => write!(f, "shares have different lengths"),
+            VsssError::SecretOutOfRange => write!(f, "secret is not smaller than the modulus"),
+            VsssError::NonInvertible => write!(f, "value is not invertible modulo the modulus"),
+            VsssError::InvalidGroupParams => write!(f, "invalid group parameters"),
+            VsssError::InvalidEncoding => write!(f, "invalid encoding"),
+            VsssError::CorruptedText { position: Some(position) } => {
+                write!(f, "corrupted share text, likely typo at position {}", position)
+            }
+            VsssError::CorruptedText { position: None } => write!(f, "corrupted share text"),
+            VsssError::InconsistentShares => write!(f, "shares are inconsistent"),
+            VsssError::AuthenticationFailed => write!(f, "ciphertext failed authentication"),
+            VsssError::NoPendingCommitment => write!(f, "no pending round commitments to answer"),
+            VsssError::ChallengeCountMismatch { expected, provided } => {
+                write!(f, "{} challenges are required but {} were provided", expected, provided)
+            }
+        }
+    }
+}
+
+impl std::error::Error for VsssError {}
+
+/// A `Result` with `VsssError` as the error type.
+pub type Result<T> = std::result::Result<T, VsssError>;
+
+/// Checks that `threshold` shares out of `num_shares` make a valid sharing.
+pub(crate) fn check_threshold(threshold: usize, num_shares: usize) -> Result<()> {
+    if threshold == 0 || threshold > num_shares {
+        return Err(VsssError::InvalidThreshold { threshold, num_shares });
+    }
+    Ok(())
+}
+
+/// Checks that the share indices `1..=num_shares` are distinct and non-zero modulo `modulus`. The
+/// share at an index divisible by the modulus would be the secret itself.
+pub(crate) fn check_share_count(num_shares: usize, modulus: &BigUint) -> Result<()> {
+    if BigUint::from(num_shares) >= *modulus {
+        let max = modulus.to_usize().map_or(usize::MAX, |modulus| modulus.saturating_sub(1));
+        return Err(VsssError::TooManyShares { num_shares, max });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_threshold() {
+        assert!(check_threshold(3, 5).is_ok());
+        assert_eq!(check_threshold(0, 5), Err(VsssError::InvalidThreshold { threshold: 0, num_shares: 5 }));
+        assert_eq!(check_threshold(6, 5), Err(VsssError::InvalidThreshold { threshold: 6, num_shares: 5 }));
+        assert_eq!(VsssError::InvalidThreshold { threshold: 6, num_shares: 5 }.to_string(), "invalid threshold 6 for 5 shares");
+    }
+
+    #[test]
+    fn test_check_share_count() {
+        assert!(check_share_count(6, &BigUint::from(7u32)).is_ok());
+        assert_eq!(check_share_count(7, &BigUint::from(7u32)), Err(VsssError::TooManyShares { num_shares: 7, max: 6 }));
+        assert_eq!(check_share_count(1, &BigUint::from(1u32)), Err(VsssError::TooManyShares { num_shares: 1, max: 0 }));
+    }
+}
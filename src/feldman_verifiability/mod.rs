@@ -11,28 +11,96 @@
 //! - Creation of public commitments to the polynomial's coefficients.
 //! - Verification of shares against the public commitments.
 //! - Reconstruction of the secret from a subset of shares using Lagrange interpolation.
+//! - Batch verification of many shares at the cost of about one share, with the small exponents test.
+//! - Verified reconstruction, which checks every share against the commitments first and reports
+//!   the rejected ones.
 //!
 //! This module requires `Polynomial`, `mod_exp`, `lagrange_interpolation_zero` and potentially other utility functions
 //! from the `utils` module for its operations.
+//!
+//! Shares are returned as typed `Share`s recording their threshold, modulus and dealing. The
+//! secret and the share values are held in `Secret`s, and the polynomial is wiped after use.
+//! `FeldmanVSSParams` implements the `Group` trait, and `generate_shares_over` and `verify_share_over`
+//! run the scheme on plain `(index, value)` tuples over any other `Group`, such as an elliptic curve.
+//!
+//! Over `Z_p^*`, powers of `g` are taken from a fixed-base comb table built on first use, and the
+//! product of powers of the commitments in the verification equation is computed with one
+//! multi-exponentiation.
 
 
-use crate::utils::{Polynomial, mod_exp,lagrange_interpolation_zero};
+use crate::algebra::{impl_subgroup_mod_p, Group, GroupElement, Scalar};
+use crate::error::{check_share_count, check_threshold, Result, VsssError};
+use crate::secret::{zeroize_biguint, Secret};
+use crate::shamirs_secret_sharing;
+use crate::utils::group_params::is_prime_order_subgroup;
+use crate::utils::{lagrange_interpolation_zero, Polynomial, Share, StandardGroup, mod_exp};
+use crate::utils::{multi_exp, share_indices_over, FixedBaseTable};
+use crate::utils::{group_id, new_dealing_id_with_rng, Scheme};
 use num_bigint::{BigUint, ToBigUint};
-use num_traits::One;
+use num_traits::{One, Zero};
+use rand::{thread_rng, CryptoRng, RngCore};
+use std::sync::OnceLock;
+use zeroize::Zeroizing;
 
 /// Represents the public parameters for the Feldman VSS scheme.
+///
+/// Shares and polynomial coefficients live in `Z_q`, while commitments are elements of the
+/// subgroup of order `q` of `Z_p^*`, generated by `g`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FeldmanVSSParams {
-    pub g: BigUint, // Generator of the group G
-    pub q: BigUint, // Prime order of the group G
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::biguint"))]
+    pub g: BigUint, // Generator of the subgroup G of order q
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::biguint"))]
+    pub p: BigUint, // Modulus of the group Z_p^*, with q | p - 1
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::biguint"))]
+    pub q: BigUint, // Prime order of the subgroup G
+    #[cfg_attr(feature = "serde", serde(skip))]
+    fixed_base: OnceLock<FixedBaseTable>, // Comb of powers of g, built on first use
 }
 
 impl FeldmanVSSParams {
 
-    /// Initializes Feldman VSS parameters with a generator and prime order.
-    pub fn new(g: BigUint, q: BigUint) -> Self {
-        FeldmanVSSParams { g, q }
+    /// Initializes Feldman VSS parameters with a generator, a group modulus and a prime subgroup order.
+    ///
+    /// The parameters are validated: `p` and `q` must be prime, `q` must divide `p - 1`, and `g`
+    /// must be an element of order exactly `q` modulo `p`.
+    ///
+    /// # Returns
+    ///
+    /// The `FeldmanVSSParams` if the parameters are consistent, otherwise `VsssError::InvalidGroupParams`,
+    /// in particular when `g` does not generate the subgroup of order `q`.
+    pub fn new(g: BigUint, p: BigUint, q: BigUint) -> Result<Self> {
+        let params = FeldmanVSSParams { g, p, q, fixed_base: OnceLock::new() };
+        if params.is_valid() {
+            Ok(params)
+        } else {
+            Err(VsssError::InvalidGroupParams)
+        }
     }
 
+    /// Initializes Feldman VSS parameters over one of the well-known RFC 3526 or RFC 7919 groups.
+    ///
+    /// The standard groups are trusted constants, so they are not re-validated.
+    pub fn from_standard_group(group: StandardGroup) -> Self {
+        FeldmanVSSParams { g: group.g(), p: group.p(), q: group.q(), fixed_base: OnceLock::new() }
+    }
+
+    /// Checks that `p` and `q` are prime, that `q` divides `p - 1`, and that `g` has order `q` modulo `p`.
+    pub fn is_valid(&self) -> bool {
+        is_prime_order_subgroup(&self.p, &self.q, &[&self.g])
+    }
+
+    /// Raises the generator `g` to `exponent` modulo `p`.
+    ///
+    /// The first call precomputes a `FixedBaseTable` of powers of `g`, which makes every later call
+    /// several times faster than `mod_exp`. If `g` or `p` were changed since, `mod_exp` is used.
+    pub fn pow_g(&self, exponent: &BigUint) -> BigUint {
+        let table = self.fixed_base.get_or_init(|| FixedBaseTable::new(&self.g, &self.p, self.q.bits() as usize));
+        if table.base != self.g || table.modulus != self.p {
+            return mod_exp(&self.g, exponent, &self.p);
+        }
+        table.pow(exponent)
+    }
 
     /// Generates shares for Shamir's Secret Sharing (SSS) scheme and creates commitments for 
     /// Feldman's Verifiable Secret Sharing (VSS) based on a provided secret, a threshold, 
@@ -42,33 +110,52 @@ impl FeldmanVSSParams {
     ///
     /// # Arguments
     ///
-    /// * `secret` - A `BigUint` representing the secret to be shared.
+    /// * `secret` - The secret to be shared, smaller than `q`.
     /// * `threshold` - The minimum number of shares required to reconstruct the secret.
     /// * `num_shares` - The total number of shares to be generated.
     ///
     /// # Returns
     ///
     /// A tuple containing two vectors:
-    /// - The first vector contains tuples of `BigUint`, each representing a share with an
-    ///   index (x-value) and the corresponding share value (y-value).
+    /// - The first vector contains the shares, each with an index (x-value) and the corresponding
+    ///   share value (y-value), tagged with the threshold, the modulus `q` and a fresh dealing identifier.
     /// - The second vector contains `BigUint` commitments to the coefficients of the polynomial,
     ///   enabling the verification of shares without revealing the coefficients themselves.
+    ///
+    /// Fails with `VsssError::InvalidThreshold` if the threshold is zero or exceeds `num_shares`, with
+    /// `VsssError::TooManyShares` if `num_shares` is not below `q`, and with
+    /// `VsssError::SecretOutOfRange` if the secret is not below `q`.
+    pub fn generate_shares(&self, secret: &Secret, threshold: usize, num_shares: usize) -> Result<(Vec<Share>, Vec<BigUint>)> {
+        self.generate_shares_with_rng(secret, threshold, num_shares, &mut thread_rng())
+    }
 
-    pub fn generate_shares(&self, secret: &BigUint, threshold: usize, num_shares: usize) -> (Vec<(BigUint, BigUint)>, Vec<BigUint>) {
-        let poly = Polynomial::new_for_shamir(threshold - 1, secret.bits() as usize, secret);
+    /// Generates shares and commitments as `generate_shares` does, drawing the polynomial
+    /// coefficients and the dealing identifier from `rng`.
+    pub fn generate_shares_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        secret: &Secret,
+        threshold: usize,
+        num_shares: usize,
+        rng: &mut R,
+    ) -> Result<(Vec<Share>, Vec<BigUint>)> {
+        check_threshold(threshold, num_shares)?;
+        check_share_count(num_shares, &self.q)?;
+        let poly = Zeroizing::new(Polynomial::new_for_shamir_mod_with_rng(threshold, secret.expose_secret(), &self.q, rng)?);
+        let group_id = group_id(&self.q);
+        let dealing_id = new_dealing_id_with_rng(rng);
         let mut shares = Vec::with_capacity(num_shares);
 
         // Generate shares using the polynomial, similar to Shamir's scheme
         for i in 1..=num_shares {
             let x = i.to_biguint().unwrap();
-            let y = poly.evaluate(&x) % &self.q; // Ensure the evaluation is done modulo q
-            shares.push((x, y));
+            let y = Secret::new(poly.evaluate(&x) % &self.q); // Ensure the evaluation is done modulo q
+            shares.push(Share::new(x, y, threshold, group_id, dealing_id, Scheme::Feldman));
         }
 
         // Generate commitments for the polynomial's coefficients for verifiability
         let commitments = self.generate_commitments(&poly);
 
-        (shares, commitments)
+        Ok((shares, commitments))
     }
     
     /// Generates verifiable commitments to the coefficients of the polynomial used in the secret sharing.
@@ -76,7 +163,7 @@ impl FeldmanVSSParams {
     /// In Feldman's Verifiable Secret Sharing scheme, these commitments are made public and allow any party
     /// to verify their shares without compromising the security of the secret or needing access to the polynomial's
     /// coefficients directly. Each commitment is calculated using the group's generator `g` raised to the power
-    /// of the coefficient, all operations performed modulo `q`, the prime order of the group.
+    /// of the coefficient, modulo the group modulus `p`.
     ///
     /// # Arguments
     ///
@@ -90,94 +177,479 @@ impl FeldmanVSSParams {
     /// These commitments can be publicly shared to enable verification of shares by participants
     /// without revealing the polynomial's coefficients or the shared secret itself.
     ///
-    /// Each commitment is of the form `g^coef mod q`, where `g` is the generator of the group,
-    /// `coef` is a coefficient of the polynomial, and `q` is the prime order of the group.
+    /// Each commitment is of the form `g^coef mod p`, where `g` is the generator of the subgroup of
+    /// order `q` and `coef` is a coefficient of the polynomial, computed with `pow_g`.
     fn generate_commitments(&self, polynomial: &Polynomial) -> Vec<BigUint> {
-        polynomial.coefficients.iter().map(|coef| {
-            mod_exp(&self.g, coef, &self.q) // Compute g^coef mod q for each coefficient
-        }).collect()
+        polynomial.coefficients.iter().map(|coef| self.pow_g(coef)).collect() // Compute g^coef mod p for each coefficient
     }
 
 }
 
+impl_subgroup_mod_p!(FeldmanVSSParams);
+
+/// Generates Feldman VSS shares and commitments over an arbitrary group.
+///
+/// The coefficients and shares are elements of the group's scalar field, and each commitment is
+/// the generator raised to the matching coefficient.
+///
+/// # Arguments
+///
+/// * `group` - The group the commitments live in.
+/// * `secret` - The secret to be shared, an element of the group's scalar field.
+/// * `threshold` - The minimum number of shares required to reconstruct the secret.
+/// * `num_shares` - The total number of shares to be generated.
+///
+/// # Returns
+///
+/// A tuple containing the `(index, value)` shares and the commitments to the coefficients. Fails
+/// with `VsssError::InvalidThreshold` if the threshold is zero or exceeds `num_shares`, and with
+/// `VsssError::TooManyShares` if a share index maps to zero or repeats.
+#[allow(clippy::type_complexity)]
+pub fn generate_shares_over<G: Group>(
+    group: &G,
+    secret: &Scalar<G>,
+    threshold: usize,
+    num_shares: usize,
+) -> Result<(Vec<(Scalar<G>, Scalar<G>)>, Vec<GroupElement<G>>)> {
+    generate_shares_over_with_rng(group, secret, threshold, num_shares, &mut thread_rng())
+}
+
+/// Generates Feldman VSS shares and commitments over an arbitrary group as `generate_shares_over`
+/// does, drawing the polynomial coefficients from `rng`.
+#[allow(clippy::type_complexity)]
+pub fn generate_shares_over_with_rng<G: Group, R: RngCore + CryptoRng>(
+    group: &G,
+    secret: &Scalar<G>,
+    threshold: usize,
+    num_shares: usize,
+    rng: &mut R,
+) -> Result<(Vec<(Scalar<G>, Scalar<G>)>, Vec<GroupElement<G>>)> {
+    check_threshold(threshold, num_shares)?;
+    let field = group.scalar_field();
+    let indices = share_indices_over(field, num_shares)?;
+    let mut poly = Polynomial::random_over_with_rng(field, secret, threshold, rng);
+
+    let shares = indices.into_iter().map(|x| {
+        let y = poly.evaluate_over(field, &x);
+        (x, y)
+    }).collect();
+    let commitments = commit_coefficients(group, &poly.coefficients);
+
+    poly.zeroize_over(field);
+    Ok((shares, commitments))
+}
+
+/// Raises the group's generator to each coefficient.
+fn commit_coefficients<G: Group>(group: &G, coefficients: &[Scalar<G>]) -> Vec<GroupElement<G>> {
+    let generator = group.generator();
+    coefficients.iter().map(|coef| group.exp(&generator, coef)).collect()
+}
+
 
 /// Verifies a share against the public commitments using the Feldman Verifiable Secret Sharing scheme.
 /// This function checks if a share is valid by verifying that g^share equals the product of the commitments
-/// raised to the power of their respective indices, all operations performed modulo q.
+/// raised to the powers of the share index, with group operations performed modulo p and exponents modulo q.
 ///
 /// # Arguments
 ///
-/// * `i` - A `BigUint` representing the index of the share being verified.
-/// * `share` - A `BigUint` representing the share value associated with the index `i`.
+/// * `share` - The `Share` being verified.
 /// * `commitments` - A slice of `BigUint` representing the public commitments to the polynomial coefficients.
-/// * `params` - A reference to the `FeldmanVSSParams` containing the public parameters (g and q) of the scheme.
+/// * `params` - A reference to the `FeldmanVSSParams` containing the public parameters (g, p and q) of the scheme.
 ///
 /// # Returns
 ///
-/// `true` if the share is valid according to the verification equation, otherwise `false`.
-
+/// `true` if the share is valid according to the verification equation, otherwise `false`. Shares
+/// of another scheme, reduced by a different modulus, or whose threshold does not match the number
+/// of commitments are rejected.
 pub fn verify_share(
-    i: &BigUint, // Share index
-    share: &BigUint, // Share value
+    share: &Share, // Share to verify
     commitments: &[BigUint], // Public commitments
     params: &FeldmanVSSParams, // VSS parameters
 ) -> bool {
-    // Calculate the left-hand side (LHS) as g^share mod q
-    let lhs = mod_exp(&params.g, share, &params.q);
+    share.scheme == Scheme::Feldman
+        && share.group_id == group_id(&params.q)
+        && share.threshold == commitments.len()
+        && params.pow_g(share.value.expose_secret()) == commitment_product(commitments, &share.index, &params.p)
+}
 
-    // Calculate the right-hand side (RHS) as the product of commitments raised to the power of the share index
-    let rhs = commitments.iter().enumerate().fold(BigUint::one(), |acc, (j, commitment)| {
-        let exponent = i.modpow(&BigUint::from(j), &params.q);
-        (acc * mod_exp(commitment, &exponent, &params.q)) % &params.q
+/// Computes the product of `commitments[j]^(i^j)` modulo `p` with one multi-exponentiation.
+fn commitment_product(commitments: &[BigUint], i: &BigUint, p: &BigUint) -> BigUint {
+    let mut exponents = Vec::with_capacity(commitments.len());
+    let mut power = BigUint::one();
+    for _ in commitments {
+        let next = &power * i;
+        exponents.push(power);
+        power = next;
+    }
+    multi_exp(commitments, &exponents, p)
+}
+
+/// Verifies a share against the public commitments over an arbitrary group, checking that the
+/// generator raised to `share` equals the product of `commitments[j]^(i^j)`.
+///
+/// The right-hand side is evaluated with Horner's rule in the exponent, so it needs one
+/// exponentiation by `i` per commitment.
+///
+/// # Returns
+///
+/// `true` if the share is valid according to the verification equation, otherwise `false`.
+pub fn verify_share_over<G: Group>(
+    group: &G,
+    i: &Scalar<G>,
+    share: &Scalar<G>,
+    commitments: &[GroupElement<G>],
+) -> bool {
+    // Calculate the left-hand side (LHS) as g^share
+    let lhs = group.exp(&group.generator(), share);
+
+    // Calculate the right-hand side (RHS) as ((C_{t-1}^i * C_{t-2})^i * ...)^i * C_0
+    let rhs = commitments.iter().rev().fold(group.identity(), |acc, commitment| {
+        group.mul(&group.exp(&acc, i), commitment)
     });
 
     lhs == rhs
 }
 
+/// Verifies many shares against the same commitments at once, with the small exponents test of
+/// Bellare, Garay and Rabin.
+///
+/// Instead of checking each share, the verification equations are combined with random 64-bit
+/// weights `r_k`: `g^(sum r_k * s_k)` must equal the product of `C_j^(sum r_k * i_k^j)`. This costs
+/// one fixed-base exponentiation and one multi-exponentiation for the whole batch instead of one
+/// of each per share, and a batch containing an invalid share passes with probability at most
+/// `2^-64`. When the batch fails, it is split in halves which are checked again, down to the
+/// invalid shares.
+///
+/// Reducing the combined exponents modulo `q` is only sound for commitments in the subgroup of
+/// order `q`, so if a commitment is not, every share is checked with `verify_share` instead.
+///
+/// # Arguments
+///
+/// * `shares` - The shares to verify.
+/// * `commitments` - The public commitments published by the dealer.
+/// * `params` - The parameters the shares and commitments were generated with.
+///
+/// # Returns
+///
+/// The positions in `shares` of the shares `verify_share` would reject, in increasing order; empty
+/// if all shares are valid.
+pub fn verify_shares_batch(shares: &[Share], commitments: &[BigUint], params: &FeldmanVSSParams) -> Vec<usize> {
+    verify_shares_batch_with_rng(shares, commitments, params, &mut thread_rng())
+}
+
+/// Verifies shares as `verify_shares_batch` does, drawing the weights from `rng`.
+pub fn verify_shares_batch_with_rng<R: RngCore + CryptoRng>(
+    shares: &[Share],
+    commitments: &[BigUint],
+    params: &FeldmanVSSParams,
+    rng: &mut R,
+) -> Vec<usize> {
+    let in_subgroup = commitments.iter().all(|commitment| {
+        !commitment.is_zero() && commitment < &params.p && mod_exp(commitment, &params.q, &params.p).is_one()
+    });
+    if !in_subgroup {
+        return (0..shares.len()).filter(|&k| !verify_share(&shares[k], commitments, params)).collect();
+    }
+
+    let mut invalid = Vec::new();
+    let mut candidates = Vec::with_capacity(shares.len());
+    for (position, share) in shares.iter().enumerate() {
+        if share.scheme == Scheme::Feldman && share.group_id == group_id(&params.q) && share.threshold == commitments.len() {
+            candidates.push(position);
+        } else {
+            invalid.push(position);
+        }
+    }
+    find_invalid_shares(shares, &candidates, commitments, params, rng, &mut invalid);
+    invalid.sort_unstable();
+    invalid
+}
+
+/// Checks the shares at `positions` as one batch and, if it fails, bisects it to push the
+/// positions of the invalid shares onto `invalid`.
+fn find_invalid_shares<R: RngCore + CryptoRng>(
+    shares: &[Share],
+    positions: &[usize],
+    commitments: &[BigUint],
+    params: &FeldmanVSSParams,
+    rng: &mut R,
+    invalid: &mut Vec<usize>,
+) {
+    if positions.is_empty() || batch_holds(shares, positions, commitments, params, rng) {
+        return;
+    }
+    if let [position] = positions {
+        invalid.push(*position);
+        return;
+    }
+    let (left, right) = positions.split_at(positions.len() / 2);
+    find_invalid_shares(shares, left, commitments, params, rng, invalid);
+    find_invalid_shares(shares, right, commitments, params, rng, invalid);
+}
+
+/// Checks the random linear combination of the verification equations of the shares at
+/// `positions`.
+fn batch_holds<R: RngCore + CryptoRng>(
+    shares: &[Share],
+    positions: &[usize],
+    commitments: &[BigUint],
+    params: &FeldmanVSSParams,
+    rng: &mut R,
+) -> bool {
+    let mut value_exponent = BigUint::zero();
+    let mut commitment_exponents = vec![BigUint::zero(); commitments.len()];
+    for &position in positions {
+        let share = &shares[position];
+        let weight = BigUint::from(rng.next_u64());
+        value_exponent = (value_exponent + &weight * share.value.expose_secret()) % &params.q;
+
+        // Accumulate r_k * i_k^j for every j.
+        let mut term = weight;
+        for exponent in commitment_exponents.iter_mut() {
+            *exponent = (&*exponent + &term) % &params.q;
+            term = term * &share.index % &params.q;
+        }
+    }
+
+    params.pow_g(&value_exponent) == multi_exp(commitments, &commitment_exponents, &params.p)
+}
+
 /// Reconstructs the secret from a set of shares using Lagrange interpolation at zero.
 /// This function is a critical part of Shamir's Secret Sharing, enabling the recovery
 /// of the secret from a minimum number of shares without revealing the shares themselves.
 ///
 /// # Arguments
 ///
-/// * `shares` - A slice of tuples containing shares, where each tuple consists of an
-///   index (x-value) and the corresponding share value (y-value).
+/// * `shares` - A slice of shares from a single dealing, at least as many as its threshold.
 /// * `modulus` - A `BigUint` representing the modulus used for the finite field operations,
 ///   which should be the same as used in share generation.
 ///
 /// # Returns
 ///
-/// An `Option<BigUint>` containing the reconstructed secret if successful, otherwise `None`.
+/// The reconstructed secret if successful, wrapped in a `Secret`, otherwise the matching
+/// `VsssError`, in particular `VsssError::MixedDealings` when the shares come from different dealings.
+pub fn reconstruct_secret(shares: &[Share], modulus: &BigUint) -> Result<Secret> {
+    shamirs_secret_sharing::reconstruct_secret(shares, modulus)
+}
 
-pub fn reconstruct_secret(shares: &[(BigUint, BigUint)], modulus: &BigUint) -> Option<BigUint> {
-    lagrange_interpolation_zero(shares, modulus)
+/// The result of `reconstruct_secret_verified`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifiedReconstruction {
+    pub secret: Secret, // The reconstructed secret
+    pub rejected: Vec<BigUint>, // Indices of the shares that failed verification
 }
 
+/// Reconstructs the secret from the shares that pass verification against the commitments.
+///
+/// Every share is checked with `verify_share`, and the invalid ones, as well as shares whose index
+/// is zero modulo `q`, are set aside. The secret is interpolated from the first `threshold` valid
+/// shares with distinct indices modulo `q`, with the threshold given by the number of commitments,
+/// and finally checked against `commitments[0]`, the commitment `g^secret`. Only the verified points
+/// are interpolated, so a valid share resubmitted under another dealing identifier or an index
+/// shifted by `q` cannot make the reconstruction fail.
+///
+/// # Arguments
+///
+/// * `shares` - The submitted shares, of which at least `commitments.len()` must be valid.
+/// * `commitments` - The public commitments published by the dealer.
+/// * `params` - The parameters the shares and commitments were generated with.
+///
+/// # Returns
+///
+/// The secret and the indices of the rejected shares. Fails with `VsssError::InsufficientShares` if
+/// fewer shares than the threshold are valid, `VsssError::InconsistentShares` if the secret does not
+/// match `commitments[0]`, and `VsssError::InvalidThreshold` if there are no commitments.
+pub fn reconstruct_secret_verified(
+    shares: &[Share],
+    commitments: &[BigUint],
+    params: &FeldmanVSSParams,
+) -> Result<VerifiedReconstruction> {
+    if commitments.is_empty() {
+        return Err(VsssError::InvalidThreshold { threshold: 0, num_shares: shares.len() });
+    }
+    let mut points: Vec<(BigUint, BigUint)> = Vec::with_capacity(commitments.len());
+    let mut rejected = Vec::new();
+    for share in shares {
+        let index = &share.index % &params.q;
+        if index.is_zero() || !verify_share(share, commitments, params) {
+            rejected.push(share.index.clone());
+        } else if points.len() < commitments.len() && points.iter().all(|(other, _)| *other != index) {
+            points.push((index, share.value.expose_secret().clone()));
+        }
+    }
+    if points.len() < commitments.len() {
+        let provided = points.len();
+        points.iter_mut().for_each(|(_, value)| zeroize_biguint(value));
+        return Err(VsssError::InsufficientShares { required: commitments.len(), provided });
+    }
+
+    let secret = lagrange_interpolation_zero(&points, &params.q).map(Secret::new);
+    points.iter_mut().for_each(|(_, value)| zeroize_biguint(value));
+    let secret = secret?;
+    if params.pow_g(secret.expose_secret()) != commitments[0] {
+        return Err(VsssError::InconsistentShares);
+    }
+    Ok(VerifiedReconstruction { secret, rejected })
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use num_bigint::ToBigUint;
-    use crate::utils::generate_prime;
+    use crate::shamirs_secret_sharing::reconstruct_secret_over;
+    use crate::utils::generate_safe_prime_group;
+
+    fn test_params() -> FeldmanVSSParams {
+        let group = generate_safe_prime_group(128).unwrap();
+        FeldmanVSSParams::new(group.g, group.p, group.q).unwrap()
+    }
 
     #[test]
     fn test_share_generation_and_verification() {
-        let secret = 1234.to_biguint().unwrap();
+        let secret = Secret::new(1234.to_biguint().unwrap());
         let threshold = 3;
         let num_shares = 5;
 
-        let g = 2.to_biguint().unwrap();
-        let q = generate_prime(256);
-
-        let params = FeldmanVSSParams::new(g, q);
+        let params = test_params();
 
-        let (shares, commitments) = params.generate_shares(&secret, threshold, num_shares);
+        let (shares, commitments) = params.generate_shares(&secret, threshold, num_shares).unwrap();
 
-        for (i, &(ref x, ref y)) in shares.iter().enumerate() {
-            assert!(verify_share(x, y, &commitments, &params), "Share {} failed verification", i + 1);
+        for (i, share) in shares.iter().enumerate() {
+            assert!(verify_share(share, &commitments, &params), "Share {} failed verification", i + 1);
         }
 
         let reconstructed_secret = reconstruct_secret(&shares[..threshold], &params.q).unwrap();
         assert_eq!(secret, reconstructed_secret, "Reconstructed secret does not match the original secret.");
     }
+
+    #[test]
+    fn test_rejects_shares_from_other_dealing() {
+        let params = test_params();
+        let (shares, commitments) = params.generate_shares(&Secret::new(11.to_biguint().unwrap()), 2, 3).unwrap();
+        let (other_shares, _) = params.generate_shares(&Secret::new(22.to_biguint().unwrap()), 2, 3).unwrap();
+
+        let mixed = vec![shares[0].clone(), other_shares[1].clone()];
+        assert_eq!(reconstruct_secret(&mixed, &params.q), Err(VsssError::MixedDealings));
+        assert!(!verify_share(&shares[0], &commitments[..1], &params));
+        let mut foreign = shares[0].clone();
+        foreign.group_id = group_id(&params.p);
+        assert!(!verify_share(&foreign, &commitments, &params));
+
+        assert_eq!(params.generate_shares(&Secret::new(params.q.clone()), 2, 3).err(), Some(VsssError::SecretOutOfRange));
+        assert_eq!(
+            params.generate_shares(&Secret::new(11.to_biguint().unwrap()), 0, 3).err(),
+            Some(VsssError::InvalidThreshold { threshold: 0, num_shares: 3 })
+        );
+    }
+
+    #[test]
+    fn test_rejects_invalid_params() {
+        let params = test_params();
+
+        // p - 1 has order 2, not q.
+        let wrong_order = &params.p - BigUint::one();
+        assert_eq!(
+            FeldmanVSSParams::new(wrong_order, params.p.clone(), params.q.clone()).err(),
+            Some(VsssError::InvalidGroupParams)
+        );
+        // q must divide p - 1.
+        let other_q = 1009.to_biguint().unwrap();
+        assert!(FeldmanVSSParams::new(params.g.clone(), params.p.clone(), other_q).is_err());
+        assert!(FeldmanVSSParams::new(BigUint::one(), params.p.clone(), params.q.clone()).is_err());
+    }
+
+    #[test]
+    fn test_standard_group() {
+        let params = FeldmanVSSParams::from_standard_group(StandardGroup::Ffdhe2048);
+        let secret = Secret::new(987654321.to_biguint().unwrap());
+        let (shares, commitments) = params.generate_shares(&secret, 2, 3).unwrap();
+
+        for share in &shares {
+            assert!(verify_share(share, &commitments, &params));
+        }
+        assert_eq!(reconstruct_secret(&shares[1..], &params.q).unwrap(), secret);
+    }
+
+    #[test]
+    fn test_generic_share_generation_and_verification() {
+        let params = test_params();
+        let secret = 77.to_biguint().unwrap();
+        let (shares, commitments) = generate_shares_over(&params, &secret, 3, 4).unwrap();
+
+        for (x, y) in &shares {
+            assert!(verify_share_over(&params, x, y, &commitments));
+        }
+        assert_eq!(reconstruct_secret_over(&params, &shares[1..]).unwrap(), secret);
+    }
+
+    #[test]
+    fn test_verified_reconstruction() {
+        let params = test_params();
+        let secret = Secret::new(4321.to_biguint().unwrap());
+        let (mut shares, commitments) = params.generate_shares(&secret, 3, 5).unwrap();
+        let (other_shares, _) = params.generate_shares(&secret, 3, 5).unwrap();
+
+        shares[0].value = Secret::new(shares[0].value.expose_secret() + 1u32);
+        shares[2] = other_shares[2].clone();
+        assert_ne!(reconstruct_secret(&[shares[0].clone(), shares[1].clone(), shares[3].clone()], &params.q).ok(), Some(secret.clone()));
+        let result = reconstruct_secret_verified(&shares, &commitments, &params).unwrap();
+        assert_eq!(result.secret, secret);
+        assert_eq!(result.rejected, vec![1.to_biguint().unwrap(), 3.to_biguint().unwrap()]);
+
+        assert_eq!(
+            reconstruct_secret_verified(&shares[..4], &commitments, &params),
+            Err(VsssError::InsufficientShares { required: 3, provided: 2 })
+        );
+    }
+
+    #[test]
+    fn test_verified_reconstruction_ignores_resubmitted_shares() {
+        let params = test_params();
+        let secret = Secret::new(4321.to_biguint().unwrap());
+        let (shares, commitments) = params.generate_shares(&secret, 3, 5).unwrap();
+
+        // A valid share resubmitted under a foreign dealing identifier, or with its index shifted by
+        // q, still verifies, but must not abort the reconstruction.
+        let mut foreign = shares[0].clone();
+        foreign.dealing_id = [0xaa; 16];
+        let mut shifted = shares[1].clone();
+        shifted.index += &params.q;
+        let mut zero = shares[2].clone();
+        zero.index = params.q.clone();
+        let submitted = vec![foreign, shares[0].clone(), shifted, shares[1].clone(), zero, shares[3].clone()];
+        assert!(reconstruct_secret(&submitted, &params.q).is_err());
+
+        let result = reconstruct_secret_verified(&submitted, &commitments, &params).unwrap();
+        assert_eq!(result.secret, secret);
+        assert_eq!(result.rejected, vec![params.q.clone()]);
+    }
+
+    #[test]
+    fn test_verify_shares_batch() {
+        let params = test_params();
+        let (mut shares, commitments) = params.generate_shares(&Secret::new(99.to_biguint().unwrap()), 4, 20).unwrap();
+        assert!(verify_shares_batch(&shares, &commitments, &params).is_empty());
+
+        shares[3].value = Secret::new(shares[3].value.expose_secret() + 1u32);
+        shares[11].value = Secret::new(BigUint::zero());
+        shares[17].scheme = Scheme::Shamir;
+        assert_eq!(verify_shares_batch(&shares, &commitments, &params), vec![3, 11, 17]);
+
+        // Outside the subgroup, the shares are verified one by one.
+        let mut outside = commitments.clone();
+        outside[1] = (&params.p - 1u32) * &outside[1] % &params.p;
+        let expected: Vec<usize> = (0..shares.len()).filter(|&k| !verify_share(&shares[k], &outside, &params)).collect();
+        assert_eq!(verify_shares_batch(&shares, &outside, &params), expected);
+    }
+
+    #[test]
+    fn test_fixed_base_generator() {
+        let mut params = FeldmanVSSParams::from_standard_group(StandardGroup::Ffdhe2048);
+        let exponent = &params.q - 12345u32;
+        assert_eq!(params.pow_g(&exponent), mod_exp(&params.g, &exponent, &params.p));
+
+        // The table is not used for a generator changed after it was built.
+        params.g = mod_exp(&params.g, &BigUint::from(2u32), &params.p);
+        assert_eq!(params.pow_g(&exponent), mod_exp(&params.g, &exponent, &params.p));
+    }
 }
@@ -0,0 +1,278 @@
+//! # GF(2^8) Secret Sharing Module
+//!
+//! This module implements Shamir's Secret Sharing over the finite field GF(2^8), in the style of
+//! `ssss` and HashiCorp Vault. Instead of encoding the whole secret as one integer below a modulus,
+//! every byte of the secret is shared independently with its own random polynomial, so secrets of
+//! any length, such as key files or passphrases, can be split directly. Each share is as long as the
+//! secret, and at most 255 shares can be issued.
+//!
+//! The field uses the AES reduction polynomial `x^8 + x^4 + x^3 + x + 1`. Two implementations of its
+//! arithmetic are available:
+//! - `Gf256::table()` uses logarithm and exponentiation tables, which is fast but performs
+//!   secret-dependent memory accesses.
+//! - `Gf256::constant_time()` multiplies with shifts and masks and inverts by exponentiation, so
+//!   its running time and memory accesses do not depend on the operands.
+//!
+//! `Gf256` implements the `PrimeField` interface, so the generic polynomial and interpolation
+//! helpers from the `utils` module are reused for share generation and reconstruction.
+
+use crate::algebra::PrimeField;
+use crate::error::{check_threshold, Result, VsssError};
+use crate::utils::{lagrange_interpolation_zero_over, Polynomial};
+use rand::{thread_rng, CryptoRng, Rng, RngCore};
+use zeroize::{Zeroize, Zeroizing};
+
+/// The reduction polynomial `x^8 + x^4 + x^3 + x + 1`, without its leading term.
+const REDUCTION: u8 = 0x1b;
+
+/// `EXP[i] = 3^i`, repeated twice so that the sum of two logarithms never needs reducing.
+const EXP: [u8; 510] = build_exp_table();
+
+/// `LOG[a]` is the discrete logarithm of `a` to the base 3. `LOG[0]` is unused.
+const LOG: [u8; 256] = build_log_table();
+
+/// Multiplies two elements of GF(2^8) without branching on or indexing by their values.
+const fn mul_constant_time(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0u8;
+    let mut i = 0;
+    while i < 8 {
+        product ^= a & (b & 1).wrapping_neg();
+        let carry = (a >> 7).wrapping_neg();
+        a = (a << 1) ^ (REDUCTION & carry);
+        b >>= 1;
+        i += 1;
+    }
+    product
+}
+
+const fn build_exp_table() -> [u8; 510] {
+    let mut table = [0u8; 510];
+    let mut value = 1u8;
+    let mut i = 0;
+    while i < 255 {
+        table[i] = value;
+        table[i + 255] = value;
+        value = mul_constant_time(value, 3);
+        i += 1;
+    }
+    table
+}
+
+const fn build_log_table() -> [u8; 256] {
+    let exp = build_exp_table();
+    let mut table = [0u8; 256];
+    let mut i = 0;
+    while i < 255 {
+        table[exp[i] as usize] = i as u8;
+        i += 1;
+    }
+    table
+}
+
+/// The finite field GF(2^8), together with the choice of arithmetic implementation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Gf256 {
+    pub constant_time: bool, // Use the constant-time arithmetic instead of the lookup tables
+}
+
+impl Gf256 {
+    /// Returns the field with table-based arithmetic.
+    pub fn table() -> Self {
+        Gf256 { constant_time: false }
+    }
+
+    /// Returns the field with constant-time arithmetic.
+    pub fn constant_time() -> Self {
+        Gf256 { constant_time: true }
+    }
+}
+
+impl PrimeField for Gf256 {
+    type Element = u8;
+
+    fn zero(&self) -> u8 {
+        0
+    }
+
+    fn one(&self) -> u8 {
+        1
+    }
+
+    fn element(&self, value: u64) -> u8 {
+        value as u8
+    }
+
+    fn add(&self, a: &u8, b: &u8) -> u8 {
+        a ^ b
+    }
+
+    fn sub(&self, a: &u8, b: &u8) -> u8 {
+        a ^ b
+    }
+
+    fn mul(&self, a: &u8, b: &u8) -> u8 {
+        if self.constant_time {
+            mul_constant_time(*a, *b)
+        } else if *a == 0 || *b == 0 {
+            0
+        } else {
+            EXP[LOG[*a as usize] as usize + LOG[*b as usize] as usize]
+        }
+    }
+
+    fn inv(&self, a: &u8) -> Option<u8> {
+        if *a == 0 {
+            None
+        } else if self.constant_time {
+            // a^254 = a^-1, computed with a fixed sequence of squarings and multiplications.
+            let mut result = 1u8;
+            let mut power = *a;
+            for _ in 1..8 {
+                power = mul_constant_time(power, power);
+                result = mul_constant_time(result, power);
+            }
+            Some(result)
+        } else {
+            Some(EXP[255 - LOG[*a as usize] as usize])
+        }
+    }
+
+    fn random_with_rng<R: RngCore + CryptoRng>(&self, rng: &mut R) -> u8 {
+        rng.gen()
+    }
+}
+
+/// Splits a byte string into shares, sharing each byte with its own random polynomial over GF(2^8).
+///
+/// # Arguments
+///
+/// * `field` - The field implementation to use, see `Gf256::table` and `Gf256::constant_time`.
+/// * `secret` - The bytes to be shared; may be of any length.
+/// * `threshold` - The minimum number of shares required to reconstruct the secret.
+/// * `num_shares` - The total number of shares to be generated, at most 255.
+///
+/// # Returns
+///
+/// A vector of `(index, bytes)` shares with indices `1..=num_shares`, each as long as the secret
+/// and wiped when dropped. Fails with `VsssError::InvalidThreshold` if the threshold is zero or exceeds `num_shares`, and
+/// with `VsssError::TooManyShares` if `num_shares` exceeds 255.
+pub fn generate_shares(field: &Gf256, secret: &[u8], threshold: usize, num_shares: usize) -> Result<Vec<(u8, Zeroizing<Vec<u8>>)>> {
+    generate_shares_with_rng(field, secret, threshold, num_shares, &mut thread_rng())
+}
+
+/// Splits a byte string into shares as `generate_shares` does, drawing the polynomial coefficients
+/// from `rng`.
+pub fn generate_shares_with_rng<R: RngCore + CryptoRng>(
+    field: &Gf256,
+    secret: &[u8],
+    threshold: usize,
+    num_shares: usize,
+    rng: &mut R,
+) -> Result<Vec<(u8, Zeroizing<Vec<u8>>)>> {
+    if num_shares > 255 {
+        return Err(VsssError::TooManyShares { num_shares, max: 255 });
+    }
+    check_threshold(threshold, num_shares)?;
+
+    let mut shares: Vec<(u8, Zeroizing<Vec<u8>>)> = (1..=num_shares as u8)
+        .map(|x| (x, Zeroizing::new(Vec::with_capacity(secret.len()))))
+        .collect();
+
+    for byte in secret {
+        let mut poly = Polynomial::random_over_with_rng(field, byte, threshold, rng);
+        for (x, values) in shares.iter_mut() {
+            values.push(poly.evaluate_over(field, x));
+        }
+        poly.coefficients.zeroize();
+    }
+
+    Ok(shares)
+}
+
+/// Reconstructs a byte string from shares produced by `generate_shares`.
+///
+/// # Arguments
+///
+/// * `field` - The field implementation to use.
+/// * `shares` - A slice of `(index, bytes)` shares, at least as many as the threshold.
+///
+/// # Returns
+///
+/// The reconstructed secret, wiped when dropped. Fails with `VsssError::InsufficientShares` if no shares are given,
+/// `VsssError::ShareLengthMismatch` if the shares differ in length, `VsssError::InvalidShareIndex`
+/// if an index is zero, and `VsssError::DuplicateShareIndex` if an index is repeated.
+pub fn reconstruct_secret<S: AsRef<[u8]>>(field: &Gf256, shares: &[(u8, S)]) -> Result<Zeroizing<Vec<u8>>> {
+    let len = shares.first().ok_or(VsssError::InsufficientShares { required: 1, provided: 0 })?.1.as_ref().len();
+    if shares.iter().any(|(_, values)| values.as_ref().len() != len) {
+        return Err(VsssError::ShareLengthMismatch);
+    }
+    if shares.iter().any(|(x, _)| *x == 0) {
+        return Err(VsssError::InvalidShareIndex);
+    }
+    if shares.iter().enumerate().any(|(i, (x, _))| shares[..i].iter().any(|(other, _)| other == x)) {
+        return Err(VsssError::DuplicateShareIndex);
+    }
+
+    let mut points = Zeroizing::new(shares.iter().map(|(x, _)| (*x, 0u8)).collect::<Vec<_>>());
+    (0..len).map(|position| {
+        for (point, (_, values)) in points.iter_mut().zip(shares) {
+            point.1 = values.as_ref()[position];
+        }
+        lagrange_interpolation_zero_over(field, &points)
+    }).collect::<Result<Vec<u8>>>().map(Zeroizing::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_arithmetic() {
+        let table = Gf256::table();
+        let constant_time = Gf256::constant_time();
+        assert_eq!(table.mul(&0x53, &0xca), 0x01);
+        for a in 0..=255u8 {
+            for b in 0..=255u8 {
+                assert_eq!(table.mul(&a, &b), constant_time.mul(&a, &b));
+            }
+            assert_eq!(table.inv(&a), constant_time.inv(&a));
+            if a != 0 {
+                assert_eq!(table.mul(&a, &table.inv(&a).unwrap()), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_and_reconstruct_shares() {
+        let secret = b"correct horse battery staple".to_vec();
+        for field in [Gf256::table(), Gf256::constant_time()] {
+            let shares = generate_shares(&field, &secret, 3, 5).unwrap();
+            assert!(shares.iter().all(|(_, values)| values.len() == secret.len()));
+
+            assert_eq!(*reconstruct_secret(&field, &shares[..3]).unwrap(), secret);
+            assert_eq!(*reconstruct_secret(&field, &shares[2..]).unwrap(), secret);
+            assert_ne!(*reconstruct_secret(&field, &shares[..2]).unwrap(), secret);
+        }
+    }
+
+    #[test]
+    fn test_rejects_invalid_input() {
+        let field = Gf256::table();
+        assert_eq!(
+            generate_shares(&field, b"secret", 0, 5),
+            Err(VsssError::InvalidThreshold { threshold: 0, num_shares: 5 })
+        );
+        assert_eq!(
+            generate_shares(&field, b"secret", 3, 256),
+            Err(VsssError::TooManyShares { num_shares: 256, max: 255 })
+        );
+
+        let mut shares = generate_shares(&field, b"secret", 2, 3).unwrap();
+        assert_eq!(reconstruct_secret::<Vec<u8>>(&field, &[]), Err(VsssError::InsufficientShares { required: 1, provided: 0 }));
+        shares[1].0 = shares[0].0;
+        assert_eq!(reconstruct_secret(&field, &shares[..2]), Err(VsssError::DuplicateShareIndex));
+        shares[2].1.pop();
+        assert_eq!(reconstruct_secret(&field, &shares[1..]), Err(VsssError::ShareLengthMismatch));
+    }
+}
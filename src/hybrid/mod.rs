@@ -0,0 +1,278 @@
+//! # Hybrid File Splitting Module
+//!
+//! Sharing a large file byte by byte, as the `gf256` module does, produces shares as large as the
+//! file. This module instead encrypts the file once under a fresh 256-bit key with an AEAD cipher,
+//! AES-256-GCM or ChaCha20-Poly1305, and shares only the key with Shamir's scheme. The output is
+//! one `EncryptedFile`, which can be stored anywhere, and small shares of the key.
+//!
+//! The header of the encrypted file, with the cipher, the dealing identifier of the key shares and
+//! the fingerprint of the modulus, is authenticated as associated data. Recombination therefore
+//! fails with `VsssError::AuthenticationFailed` if the ciphertext or its header was tampered with,
+//! or if the shares do not reconstruct the right key.
+
+use crate::error::{Result, VsssError};
+use crate::secret::Secret;
+use crate::shamirs_secret_sharing;
+use crate::utils::{group_id, DealingId, GroupId, Share};
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::Aes256Gcm;
+use chacha20poly1305::ChaCha20Poly1305;
+use num_bigint::BigUint;
+use rand::{thread_rng, CryptoRng, RngCore};
+use zeroize::Zeroizing;
+
+/// The magic bytes an encrypted file starts with.
+pub const MAGIC: [u8; 4] = *b"VSSE";
+
+/// The current version of the encrypted file format.
+pub const VERSION: u8 = 1;
+
+/// The length of the encryption key in bytes.
+pub const KEY_LEN: usize = 32;
+
+/// The length of the nonce in bytes.
+pub const NONCE_LEN: usize = 12;
+
+/// The length of the authentication tag in bytes.
+pub const TAG_LEN: usize = 16;
+
+/// The length of the header of an encrypted file: magic bytes, version, cipher, dealing
+/// identifier, group identifier and nonce.
+const HEADER_LEN: usize = 4 + 1 + 1 + 16 + 32 + NONCE_LEN;
+
+/// The AEAD cipher a file is encrypted with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Cipher {
+    /// AES-256 in Galois/Counter Mode, fastest on processors with AES instructions.
+    Aes256Gcm = 1,
+    /// ChaCha20-Poly1305, fast and constant-time in software.
+    ChaCha20Poly1305 = 2,
+}
+
+/// A file encrypted under a key that was split into shares.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EncryptedFile {
+    pub cipher: Cipher, // AEAD cipher the file is encrypted with
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::bytes"))]
+    pub dealing_id: DealingId, // Dealing identifier of the key shares
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::bytes"))]
+    pub group_id: GroupId, // Fingerprint of the modulus the key shares are reduced by
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::bytes"))]
+    pub nonce: [u8; NONCE_LEN], // Random nonce of the encryption
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::bytes"))]
+    pub ciphertext: Vec<u8>, // Encrypted file followed by the authentication tag
+}
+
+impl EncryptedFile {
+    /// Returns the header of the file, which is authenticated as associated data.
+    fn header(&self) -> Vec<u8> {
+        let mut header = MAGIC.to_vec();
+        header.extend_from_slice(&[VERSION, self.cipher as u8]);
+        header.extend_from_slice(&self.dealing_id);
+        header.extend_from_slice(&self.group_id);
+        header.extend_from_slice(&self.nonce);
+        header
+    }
+
+    /// Encodes the file as its header followed by the ciphertext.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.header();
+        bytes.extend_from_slice(&self.ciphertext);
+        bytes
+    }
+
+    /// Decodes a file encoded by `to_bytes`.
+    ///
+    /// # Returns
+    ///
+    /// The encrypted file, or `VsssError::InvalidEncoding` if the bytes are too short to hold a
+    /// header and a tag, or have unknown magic bytes, version or cipher.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < HEADER_LEN + TAG_LEN || bytes[..4] != MAGIC || bytes[4] != VERSION {
+            return Err(VsssError::InvalidEncoding);
+        }
+        let cipher = match bytes[5] {
+            1 => Cipher::Aes256Gcm,
+            2 => Cipher::ChaCha20Poly1305,
+            _ => return Err(VsssError::InvalidEncoding),
+        };
+        Ok(EncryptedFile {
+            cipher,
+            dealing_id: array_at(bytes, 6),
+            group_id: array_at(bytes, 22),
+            nonce: array_at(bytes, 54),
+            ciphertext: bytes[HEADER_LEN..].to_vec(),
+        })
+    }
+}
+
+/// Copies the `N` bytes of `bytes` starting at `start`, which the caller has checked to exist.
+fn array_at<const N: usize>(bytes: &[u8], start: usize) -> [u8; N] {
+    let mut array = [0u8; N];
+    array.copy_from_slice(&bytes[start..start + N]);
+    array
+}
+
+/// Encrypts a file under a fresh random key and splits the key into Shamir shares.
+///
+/// # Arguments
+///
+/// * `plaintext` - The contents of the file.
+/// * `cipher` - The AEAD cipher to encrypt with.
+/// * `threshold` - The minimum number of shares required to decrypt the file.
+/// * `num_shares` - The total number of shares to generate.
+/// * `modulus` - A prime modulus of more than 256 bits, so that every key fits below it.
+///
+/// # Returns
+///
+/// The shares of the key and the encrypted file, `VsssError::SecretOutOfRange` if the modulus has
+/// 256 bits or fewer, or the errors of `shamirs_secret_sharing::generate_shares`.
+pub fn split_file(
+    plaintext: &[u8],
+    cipher: Cipher,
+    threshold: usize,
+    num_shares: usize,
+    modulus: &BigUint,
+) -> Result<(Vec<Share>, EncryptedFile)> {
+    split_file_with_rng(plaintext, cipher, threshold, num_shares, modulus, &mut thread_rng())
+}
+
+/// Encrypts a file and splits the key as `split_file` does, drawing the key, the nonce and the
+/// shares from `rng`.
+pub fn split_file_with_rng<R: RngCore + CryptoRng>(
+    plaintext: &[u8],
+    cipher: Cipher,
+    threshold: usize,
+    num_shares: usize,
+    modulus: &BigUint,
+    rng: &mut R,
+) -> Result<(Vec<Share>, EncryptedFile)> {
+    if modulus.bits() <= 8 * KEY_LEN as u64 {
+        return Err(VsssError::SecretOutOfRange);
+    }
+    let mut key = Zeroizing::new([0u8; KEY_LEN]);
+    rng.fill_bytes(key.as_mut());
+    let secret = Secret::new(BigUint::from_bytes_be(key.as_ref()));
+    let shares = shamirs_secret_sharing::generate_shares_with_rng(&secret, threshold, num_shares, modulus, rng)?;
+
+    let mut nonce = [0u8; NONCE_LEN];
+    rng.fill_bytes(&mut nonce);
+    let mut file = EncryptedFile {
+        cipher,
+        dealing_id: shares[0].dealing_id,
+        group_id: group_id(modulus),
+        nonce,
+        ciphertext: Vec::new(),
+    };
+    let payload = Payload { msg: plaintext, aad: &file.header() };
+    file.ciphertext = match cipher {
+        Cipher::Aes256Gcm => Aes256Gcm::new(key.as_ref().into()).encrypt(&nonce.into(), payload),
+        Cipher::ChaCha20Poly1305 => ChaCha20Poly1305::new(key.as_ref().into()).encrypt(&nonce.into(), payload),
+    }
+    .map_err(|_| VsssError::InvalidEncoding)?;
+    Ok((shares, file))
+}
+
+/// Reconstructs the key from shares and decrypts the file.
+///
+/// # Arguments
+///
+/// * `shares` - Shares of the key, at least as many as the threshold.
+/// * `file` - The encrypted file produced with the shares.
+/// * `modulus` - The modulus the shares were generated with.
+///
+/// # Returns
+///
+/// The contents of the file. Fails with `VsssError::MixedDealings` if the shares were produced for
+/// another file, `VsssError::GroupMismatch` if the file was encrypted for another modulus,
+/// `VsssError::AuthenticationFailed` if the file was tampered with or the shares do not
+/// reconstruct its key, or the errors of `shamirs_secret_sharing::reconstruct_secret`.
+pub fn combine_file(shares: &[Share], file: &EncryptedFile, modulus: &BigUint) -> Result<Zeroizing<Vec<u8>>> {
+    if file.group_id != group_id(modulus) {
+        return Err(VsssError::GroupMismatch);
+    }
+    if shares.iter().any(|share| share.dealing_id != file.dealing_id) {
+        return Err(VsssError::MixedDealings);
+    }
+    let secret = shamirs_secret_sharing::reconstruct_secret(shares, modulus)?;
+    let digits = Zeroizing::new(secret.expose_secret().to_bytes_be());
+    if digits.len() > KEY_LEN {
+        return Err(VsssError::AuthenticationFailed);
+    }
+    let mut key = Zeroizing::new([0u8; KEY_LEN]);
+    key[KEY_LEN - digits.len()..].copy_from_slice(&digits);
+
+    let payload = Payload { msg: &file.ciphertext, aad: &file.header() };
+    let nonce = file.nonce.into();
+    match file.cipher {
+        Cipher::Aes256Gcm => Aes256Gcm::new(key.as_ref().into()).decrypt(&nonce, payload),
+        Cipher::ChaCha20Poly1305 => ChaCha20Poly1305::new(key.as_ref().into()).decrypt(&nonce, payload),
+    }
+    .map(Zeroizing::new)
+    .map_err(|_| VsssError::AuthenticationFailed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::StandardGroup;
+
+    #[test]
+    fn test_split_and_combine_file() {
+        let modulus = StandardGroup::Ffdhe2048.q();
+        let plaintext = vec![0x5a; 100_000];
+        for cipher in [Cipher::Aes256Gcm, Cipher::ChaCha20Poly1305] {
+            let (shares, file) = split_file(&plaintext, cipher, 3, 5, &modulus).unwrap();
+            assert_eq!(file.ciphertext.len(), plaintext.len() + TAG_LEN);
+
+            let decoded = EncryptedFile::from_bytes(&file.to_bytes()).unwrap();
+            assert_eq!(decoded, file);
+            assert_eq!(combine_file(&shares[2..], &decoded, &modulus).unwrap().as_slice(), plaintext);
+            assert_eq!(
+                combine_file(&shares[..2], &file, &modulus),
+                Err(VsssError::InsufficientShares { required: 3, provided: 2 })
+            );
+        }
+    }
+
+    #[test]
+    fn test_detects_tampering() {
+        let modulus = StandardGroup::Ffdhe2048.q();
+        let (shares, file) = split_file(b"attack at dawn", Cipher::ChaCha20Poly1305, 2, 3, &modulus).unwrap();
+
+        let mut tampered = file.clone();
+        tampered.ciphertext[3] ^= 1;
+        assert_eq!(combine_file(&shares[..2], &tampered, &modulus), Err(VsssError::AuthenticationFailed));
+        let mut tampered = file.clone();
+        tampered.cipher = Cipher::Aes256Gcm;
+        assert_eq!(combine_file(&shares[..2], &tampered, &modulus), Err(VsssError::AuthenticationFailed));
+
+        let mut wrong_key = shares.clone();
+        wrong_key[0].value = Secret::new(wrong_key[0].value.expose_secret() + 1u32);
+        assert!(combine_file(&wrong_key[..2], &file, &modulus).is_err());
+
+        let (other, _) = split_file(b"attack at dawn", Cipher::ChaCha20Poly1305, 2, 3, &modulus).unwrap();
+        assert_eq!(combine_file(&other[..2], &file, &modulus), Err(VsssError::MixedDealings));
+        assert_eq!(split_file(b"", Cipher::Aes256Gcm, 2, 3, &BigUint::from(65537u32)).err(), Some(VsssError::SecretOutOfRange));
+        assert_eq!(EncryptedFile::from_bytes(&file.to_bytes()[..HEADER_LEN + TAG_LEN - 1]), Err(VsssError::InvalidEncoding));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_encrypted_file_encoding() {
+        let modulus = StandardGroup::Ffdhe2048.q();
+        let (shares, file) = split_file(b"attack at dawn", Cipher::Aes256Gcm, 2, 3, &modulus).unwrap();
+
+        let json = serde_json::to_string(&file).unwrap();
+        assert!(json.starts_with(r#"{"cipher":"Aes256Gcm","dealing_id":""#));
+        assert!(json.contains(&format!(r#""nonce":"{}""#, hex::encode(file.nonce))));
+        let decoded: EncryptedFile = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, file);
+        assert_eq!(combine_file(&shares[1..], &decoded, &modulus).unwrap().as_slice(), b"attack at dawn");
+
+        let nonce = hex::encode(file.nonce);
+        assert!(serde_json::from_str::<EncryptedFile>(&json.replace(&nonce, &nonce[2..])).is_err());
+    }
+}
@@ -1,4 +1,14 @@
+pub mod algebra;
+pub mod error;
+pub mod secret;
 pub mod utils;
 pub mod shamirs_secret_sharing;
 pub mod feldman_verifiability;
-pub mod benaloh_zk;
\ No newline at end of file
+pub mod pedersen_vss;
+pub mod ec_vss;
+pub mod benaloh_zk;
+pub mod gf256;
+#[cfg(feature = "slip39")]
+pub mod slip39;
+#[cfg(feature = "hybrid")]
+pub mod hybrid;
\ No newline at end of file
@@ -0,0 +1,371 @@
+//! # Pedersen's Verifiable Secret Sharing (VSS) Module
+//!
+//! This module implements Pedersen's Verifiable Secret Sharing scheme. Like Feldman's VSS it
+//! publishes commitments to the coefficients of the sharing polynomial so that every participant
+//! can verify their share, but each commitment is blinded with the matching coefficient of a second,
+//! random polynomial: `C_j = g^a_j * h^b_j mod p`.
+//!
+//! Because of the blinding, the commitments are information-theoretically hiding: unlike Feldman's
+//! `g^secret`, they reveal nothing about the secret, even to an unbounded adversary. This makes the
+//! scheme suitable for low-entropy secrets. Binding relies on nobody knowing `log_g(h)`.
+//!
+//! The key functionalities include:
+//! - Generation of `(share, blinding_share)` pairs based on a secret.
+//! - Creation of public blinded commitments to the polynomials' coefficients.
+//! - Verification of shares against the public commitments.
+//! - Reconstruction of the secret from a subset of shares using Lagrange interpolation.
+//!
+//! Shares and blinding shares are returned as typed `Share`s recording their threshold, modulus
+//! and dealing. `PedersenVSSParams` implements the `Group` trait, and `generate_shares_over` and
+//! `verify_share_over` run the scheme on plain tuples over any other `Group` given a second
+//! generator `h`.
+
+use crate::algebra::{impl_subgroup_mod_p, Group, GroupElement, PrimeField, Scalar};
+use crate::error::{check_threshold, Result, VsssError};
+use crate::secret::Secret;
+use crate::shamirs_secret_sharing;
+use crate::utils::group_params::is_prime_order_subgroup;
+use crate::utils::{derive_generator, group_id, new_dealing_id_with_rng, Scheme};
+use crate::utils::{share_indices_over, Polynomial, Share, StandardGroup};
+use num_bigint::BigUint;
+use rand::{thread_rng, CryptoRng, RngCore};
+
+/// Represents the public parameters for the Pedersen VSS scheme.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PedersenVSSParams {
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::biguint"))]
+    pub g: BigUint, // Generator of the subgroup of order q
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::biguint"))]
+    pub h: BigUint, // Second generator of the subgroup with unknown discrete log base g
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::biguint"))]
+    pub p: BigUint, // Modulus of the group Z_p^*
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::biguint"))]
+    pub q: BigUint, // Prime order of the subgroup
+}
+
+impl PedersenVSSParams {
+    /// Initializes Pedersen VSS parameters with two generators, the group modulus and the subgroup order.
+    ///
+    /// The parameters are validated as by `FeldmanVSSParams::new`: `p` and `q` must be prime, `q`
+    /// must divide `p - 1`, and both `g` and `h` must be elements of order exactly `q` modulo `p`.
+    ///
+    /// # Returns
+    ///
+    /// The `PedersenVSSParams` if the parameters are consistent, otherwise `VsssError::InvalidGroupParams`.
+    pub fn new(g: BigUint, h: BigUint, p: BigUint, q: BigUint) -> Result<Self> {
+        let params = PedersenVSSParams { g, h, p, q };
+        if params.is_valid() {
+            Ok(params)
+        } else {
+            Err(VsssError::InvalidGroupParams)
+        }
+    }
+
+    /// Initializes Pedersen VSS parameters from a single generator `g`, deriving `h` by hashing
+    /// into the subgroup so that `log_g(h)` is unknown to everyone, including the dealer.
+    ///
+    /// # Returns
+    ///
+    /// The `PedersenVSSParams` if the parameters pass the checks of `new`, otherwise
+    /// `VsssError::InvalidGroupParams`.
+    pub fn from_group(g: BigUint, p: BigUint, q: BigUint) -> Result<Self> {
+        let h = blinding_generator(&g, &p, &q)?;
+        PedersenVSSParams::new(g, h, p, q)
+    }
+
+    /// Initializes Pedersen VSS parameters over one of the well-known RFC 3526 or RFC 7919 groups,
+    /// with `h` derived as in `from_group`.
+    ///
+    /// The standard groups are trusted constants, so they are not re-validated.
+    pub fn from_standard_group(group: StandardGroup) -> Self {
+        let (g, p, q) = (group.g(), group.p(), group.q());
+        let h = blinding_generator(&g, &p, &q).expect("standard groups have a subgroup of order q");
+        PedersenVSSParams { g, h, p, q }
+    }
+
+    /// Checks that `p` and `q` are prime, that `q` divides `p - 1`, and that `g` and `h` have order
+    /// `q` modulo `p`.
+    pub fn is_valid(&self) -> bool {
+        is_prime_order_subgroup(&self.p, &self.q, &[&self.g, &self.h])
+    }
+
+    /// Generates shares of a secret together with shares of a random blinding polynomial, and
+    /// creates the blinded commitments to the coefficients of both polynomials.
+    ///
+    /// # Arguments
+    ///
+    /// * `secret` - The secret to be shared, smaller than `q`.
+    /// * `threshold` - The minimum number of shares required to reconstruct the secret.
+    /// * `num_shares` - The total number of shares to be generated.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing two vectors:
+    /// - The first vector contains one `(share, blinding_share)` pair per participant, both tagged
+    ///   with the same dealing identifier. Both halves of the pair are handed privately to the participant.
+    /// - The second vector contains the `BigUint` commitments `g^a_j * h^b_j mod p`.
+    ///
+    /// Fails with `VsssError::InvalidThreshold` if the threshold is zero or exceeds `num_shares`, with
+    /// `VsssError::TooManyShares` if `num_shares` is not below `q`, and with
+    /// `VsssError::SecretOutOfRange` if the secret is not below `q`.
+    #[allow(clippy::type_complexity)]
+    pub fn generate_shares(&self, secret: &Secret, threshold: usize, num_shares: usize) -> Result<(Vec<(Share, Share)>, Vec<BigUint>)> {
+        self.generate_shares_with_rng(secret, threshold, num_shares, &mut thread_rng())
+    }
+
+    /// Generates shares, blinding shares and commitments as `generate_shares` does, drawing both
+    /// polynomials and the dealing identifier from `rng`.
+    #[allow(clippy::type_complexity)]
+    pub fn generate_shares_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        secret: &Secret,
+        threshold: usize,
+        num_shares: usize,
+        rng: &mut R,
+    ) -> Result<(Vec<(Share, Share)>, Vec<BigUint>)> {
+        if secret.expose_secret() >= &self.q {
+            return Err(VsssError::SecretOutOfRange);
+        }
+        let (points, commitments) = generate_shares_over_with_rng(self, &self.h, secret.expose_secret(), threshold, num_shares, rng)?;
+        let group_id = group_id(&self.q);
+        let dealing_id = new_dealing_id_with_rng(rng);
+
+        let shares = points.into_iter().map(|((x, y), (_, z))| {
+            let blinding_share = Share::new(x.clone(), Secret::new(z), threshold, group_id, dealing_id, Scheme::PedersenBlinding);
+            (Share::new(x, Secret::new(y), threshold, group_id, dealing_id, Scheme::Pedersen), blinding_share)
+        }).collect();
+
+        Ok((shares, commitments))
+    }
+}
+
+impl_subgroup_mod_p!(PedersenVSSParams);
+
+/// Derives the second generator `h` from `g` by hashing into the subgroup of order `q`.
+fn blinding_generator(g: &BigUint, p: &BigUint, q: &BigUint) -> Result<BigUint> {
+    let mut seed = b"pedersen_vss".to_vec();
+    seed.extend_from_slice(&g.to_bytes_be());
+    derive_generator(p, q, &seed, 1)
+}
+
+/// Generates Pedersen VSS shares, blinding shares and commitments over an arbitrary group.
+///
+/// # Arguments
+///
+/// * `group` - The group the commitments live in; its generator plays the role of `g`.
+/// * `h` - A second generator of the group whose discrete logarithm base `g` is unknown.
+/// * `secret` - The secret to be shared, an element of the group's scalar field.
+/// * `threshold` - The minimum number of shares required to reconstruct the secret.
+/// * `num_shares` - The total number of shares to be generated.
+///
+/// # Returns
+///
+/// A tuple containing one `(share, blinding_share)` pair per participant and the commitments
+/// `g^a_j * h^b_j`. Fails with `VsssError::InvalidThreshold` if the threshold is zero or exceeds
+/// `num_shares`, and with `VsssError::TooManyShares` if a share index maps to zero or repeats.
+#[allow(clippy::type_complexity)]
+pub fn generate_shares_over<G: Group>(
+    group: &G,
+    h: &GroupElement<G>,
+    secret: &Scalar<G>,
+    threshold: usize,
+    num_shares: usize,
+) -> Result<(Vec<((Scalar<G>, Scalar<G>), (Scalar<G>, Scalar<G>))>, Vec<GroupElement<G>>)> {
+    generate_shares_over_with_rng(group, h, secret, threshold, num_shares, &mut thread_rng())
+}
+
+/// Generates Pedersen VSS shares over an arbitrary group as `generate_shares_over` does, drawing
+/// both polynomials from `rng`.
+#[allow(clippy::type_complexity)]
+pub fn generate_shares_over_with_rng<G: Group, R: RngCore + CryptoRng>(
+    group: &G,
+    h: &GroupElement<G>,
+    secret: &Scalar<G>,
+    threshold: usize,
+    num_shares: usize,
+    rng: &mut R,
+) -> Result<(Vec<((Scalar<G>, Scalar<G>), (Scalar<G>, Scalar<G>))>, Vec<GroupElement<G>>)> {
+    check_threshold(threshold, num_shares)?;
+    let field = group.scalar_field();
+    let indices = share_indices_over(field, num_shares)?;
+    let mut poly = Polynomial::random_over_with_rng(field, secret, threshold, rng);
+    let mut blinding = field.random_with_rng(rng);
+    let mut blinding_poly = Polynomial::random_over_with_rng(field, &blinding, threshold, rng);
+    let mut shares = Vec::with_capacity(num_shares);
+
+    for x in indices {
+        let y = poly.evaluate_over(field, &x);
+        let z = blinding_poly.evaluate_over(field, &x);
+        shares.push(((x.clone(), y), (x, z)));
+    }
+
+    let g = group.generator();
+    let commitments = poly.coefficients.iter().zip(&blinding_poly.coefficients).map(|(a, b)| {
+        group.mul(&group.exp(&g, a), &group.exp(h, b))
+    }).collect();
+
+    // Both polynomials determine the secret, so they are wiped as soon as the shares are out.
+    poly.zeroize_over(field);
+    blinding_poly.zeroize_over(field);
+    field.zeroize_element(&mut blinding);
+    Ok((shares, commitments))
+}
+
+/// Verifies a share and its blinding share against the public commitments using Pedersen's
+/// Verifiable Secret Sharing scheme. This function checks that `g^share * h^blinding_share` equals
+/// the product of the commitments raised to the powers of the share index, modulo p.
+///
+/// # Arguments
+///
+/// * `share` - The `Share` being verified.
+/// * `blinding_share` - The blinding share handed out together with `share`.
+/// * `commitments` - A slice of `BigUint` representing the public commitments.
+/// * `params` - A reference to the `PedersenVSSParams` containing the public parameters of the scheme.
+///
+/// # Returns
+///
+/// `true` if the share is valid according to the verification equation, otherwise `false`. The
+/// share and blinding share must be a Pedersen share and blinding share of the same dealing,
+/// index and modulus `q`, be reduced modulo `q`, and both have a threshold matching the number
+/// of commitments.
+pub fn verify_share(
+    share: &Share, // Share to verify
+    blinding_share: &Share, // Matching blinding share
+    commitments: &[BigUint], // Public commitments
+    params: &PedersenVSSParams, // VSS parameters
+) -> bool {
+    share.scheme == Scheme::Pedersen
+        && blinding_share.scheme == Scheme::PedersenBlinding
+        && share.group_id == group_id(&params.q)
+        && share.threshold == commitments.len()
+        && blinding_share.group_id == share.group_id
+        && blinding_share.threshold == share.threshold
+        && blinding_share.dealing_id == share.dealing_id
+        && blinding_share.index == share.index
+        && verify_share_over(params, &params.h, &share.index, share.value.expose_secret(), blinding_share.value.expose_secret(), commitments)
+}
+
+/// Verifies a share and its blinding share against Pedersen commitments over an arbitrary group,
+/// checking that `g^share * h^blinding_share` equals the product of `commitments[j]^(i^j)`.
+///
+/// # Returns
+///
+/// `true` if the share is valid according to the verification equation, otherwise `false`.
+pub fn verify_share_over<G: Group>(
+    group: &G,
+    h: &GroupElement<G>,
+    i: &Scalar<G>,
+    share: &Scalar<G>,
+    blinding_share: &Scalar<G>,
+    commitments: &[GroupElement<G>],
+) -> bool {
+    // Calculate the left-hand side (LHS) as g^share * h^blinding_share
+    let lhs = group.mul(&group.exp(&group.generator(), share), &group.exp(h, blinding_share));
+
+    // Calculate the right-hand side (RHS) with Horner's rule in the exponent
+    let rhs = commitments.iter().rev().fold(group.identity(), |acc, commitment| {
+        group.mul(&group.exp(&acc, i), commitment)
+    });
+
+    lhs == rhs
+}
+
+/// Reconstructs the secret from a set of shares using Lagrange interpolation at zero.
+///
+/// # Arguments
+///
+/// * `shares` - A slice of shares from a single dealing. The blinding shares are not needed.
+/// * `modulus` - The subgroup order `q` used when the shares were generated.
+///
+/// # Returns
+///
+/// The reconstructed secret if successful, wrapped in a `Secret`, otherwise the matching
+/// `VsssError`, in particular `VsssError::MixedDealings` when the shares come from different dealings.
+pub fn reconstruct_secret(shares: &[Share], modulus: &BigUint) -> Result<Secret> {
+    shamirs_secret_sharing::reconstruct_secret(shares, modulus)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::generate_safe_prime_group;
+    use num_bigint::ToBigUint;
+    use num_traits::One;
+
+    fn test_params() -> PedersenVSSParams {
+        let group = generate_safe_prime_group(128).unwrap();
+        PedersenVSSParams::from_group(group.g, group.p, group.q).unwrap()
+    }
+
+    #[test]
+    fn test_share_generation_and_verification() {
+        let params = test_params();
+        let secret = Secret::new(1234.to_biguint().unwrap());
+        let threshold = 3;
+        let num_shares = 5;
+
+        let (shares, commitments) = params.generate_shares(&secret, threshold, num_shares).unwrap();
+        assert_eq!(commitments.len(), threshold);
+
+        for (i, (share, blinding_share)) in shares.iter().enumerate() {
+            assert!(verify_share(share, blinding_share, &commitments, &params), "Share {} failed verification", i + 1);
+        }
+
+        let secret_shares: Vec<Share> = shares.iter().map(|(share, _)| share.clone()).collect();
+        let reconstructed_secret = reconstruct_secret(&secret_shares[..threshold], &params.q).unwrap();
+        assert_eq!(secret, reconstructed_secret, "Reconstructed secret does not match the original secret.");
+    }
+
+    #[test]
+    fn test_rejects_tampered_share() {
+        let params = test_params();
+        let (shares, commitments) = params.generate_shares(&Secret::new(99.to_biguint().unwrap()), 2, 3).unwrap();
+        let (share, blinding_share) = &shares[0];
+        let mut tampered = share.clone();
+        tampered.value = Secret::new((share.value.expose_secret() + BigUint::one()) % &params.q);
+        assert!(!verify_share(&tampered, blinding_share, &commitments, &params));
+        let mut tampered_blinding = blinding_share.clone();
+        tampered_blinding.value = Secret::new((blinding_share.value.expose_secret() + BigUint::one()) % &params.q);
+        assert!(!verify_share(share, &tampered_blinding, &commitments, &params));
+        let mut relabeled_blinding = blinding_share.clone();
+        relabeled_blinding.threshold += 1;
+        assert!(!verify_share(share, &relabeled_blinding, &commitments, &params));
+        relabeled_blinding = blinding_share.clone();
+        relabeled_blinding.group_id = group_id(&params.p);
+        assert!(!verify_share(share, &relabeled_blinding, &commitments, &params));
+        assert!(!verify_share(share, &shares[1].1, &commitments, &params));
+    }
+
+    #[test]
+    fn test_rejects_invalid_params() {
+        let params = test_params();
+        let (g, h, p, q) = (params.g.clone(), params.h.clone(), params.p.clone(), params.q.clone());
+        assert!(PedersenVSSParams::new(g.clone(), h.clone(), p.clone(), q.clone()).is_ok());
+
+        // p - 1 has order 2, not q, whether it stands for g or for h.
+        let wrong_order = &p - BigUint::one();
+        assert_eq!(
+            PedersenVSSParams::new(g.clone(), wrong_order.clone(), p.clone(), q.clone()).err(),
+            Some(VsssError::InvalidGroupParams)
+        );
+        assert!(PedersenVSSParams::new(wrong_order, h.clone(), p.clone(), q.clone()).is_err());
+        assert!(PedersenVSSParams::new(g.clone(), BigUint::one(), p.clone(), q).is_err());
+        // q must divide p - 1.
+        assert!(PedersenVSSParams::new(g.clone(), h, p.clone(), 1009.to_biguint().unwrap()).is_err());
+        assert_eq!(PedersenVSSParams::from_group(g, p, 1009.to_biguint().unwrap()).err(), Some(VsssError::InvalidGroupParams));
+        // No element of Z_2^* can generate a subgroup.
+        assert!(PedersenVSSParams::from_group(BigUint::one(), 2.to_biguint().unwrap(), BigUint::one()).is_err());
+    }
+
+    #[test]
+    fn test_standard_group() {
+        let params = PedersenVSSParams::from_standard_group(StandardGroup::Modp2048);
+        assert!(params.is_valid());
+        let secret = Secret::new(5.to_biguint().unwrap());
+        let (shares, commitments) = params.generate_shares(&secret, 2, 3).unwrap();
+
+        for (share, blinding_share) in &shares {
+            assert!(verify_share(share, blinding_share, &commitments, &params));
+        }
+    }
+}
@@ -0,0 +1,119 @@
+//! # Secret Module
+//!
+//! This module defines `Secret`, a wrapper around a `BigUint` holding secret material: the secret
+//! being shared, the value of a share, or a reconstructed secret. A `Secret` is overwritten when it
+//! is dropped, and its `Debug` and `Display` implementations print `<redacted>` instead of the
+//! value, so secrets do not end up in logs, panic messages or core dumps by accident. The value is
+//! only reachable through `Secret::expose_secret`, which makes every use of it explicit.
+//!
+//! Wiping is best effort: the temporaries created inside `num-bigint` arithmetic, and copies taken
+//! with `expose_secret().clone()`, are not covered.
+
+use num_bigint::BigUint;
+use num_traits::Zero;
+use std::fmt;
+use std::sync::atomic::{compiler_fence, Ordering};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// A secret `BigUint` that is wiped on drop and redacted when formatted.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Secret(BigUint);
+
+impl Secret {
+    /// Wraps a secret value.
+    pub fn new(value: BigUint) -> Self {
+        Secret(value)
+    }
+
+    /// Returns the secret value. Callers are responsible for not leaking it.
+    pub fn expose_secret(&self) -> &BigUint {
+        &self.0
+    }
+}
+
+impl From<BigUint> for Secret {
+    fn from(value: BigUint) -> Self {
+        Secret(value)
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Secret(<redacted>)")
+    }
+}
+
+impl fmt::Display for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<redacted>")
+    }
+}
+
+impl Zeroize for Secret {
+    fn zeroize(&mut self) {
+        zeroize_biguint(&mut self.0);
+    }
+}
+
+impl Drop for Secret {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for Secret {}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Secret {
+    /// Serializes the value as a big-endian hexadecimal string. Whoever stores or transmits the
+    /// encoding is responsible for protecting it.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        crate::utils::serialization::biguint::serialize(&self.0, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Secret {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        crate::utils::serialization::biguint::deserialize(deserializer).map(Secret)
+    }
+}
+
+/// Overwrites the digits of `value` in place and sets it to zero.
+///
+/// `BigUint` does not expose its digit buffer, so the digits are overwritten by assigning a value
+/// of the same length, which `assign_from_slice` writes into the existing allocation.
+pub(crate) fn zeroize_biguint(value: &mut BigUint) {
+    if value.is_zero() {
+        return;
+    }
+    let ones = vec![u32::MAX; value.iter_u32_digits().len()];
+    value.assign_from_slice(&ones);
+    value.set_zero();
+    compiler_fence(Ordering::SeqCst);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redacted_formatting() {
+        let secret = Secret::new(BigUint::from(123456789u32));
+        assert_eq!(format!("{}", secret), "<redacted>");
+        assert_eq!(format!("{:?}", secret), "Secret(<redacted>)");
+        assert!(!format!("{:?}", Some(secret.clone())).contains("123456789"));
+        assert_eq!(secret.expose_secret(), &BigUint::from(123456789u32));
+    }
+
+    #[test]
+    fn test_zeroize() {
+        let mut secret = Secret::new(BigUint::from(u128::MAX) << 100);
+        secret.zeroize();
+        assert!(secret.expose_secret().is_zero());
+
+        let mut value = BigUint::from(0x1234_5678_9abc_def0u64);
+        zeroize_biguint(&mut value);
+        assert!(value.is_zero());
+    }
+}
@@ -21,10 +21,42 @@
 //! is necessary. It leverages the `Polynomial` and `lagrange_interpolation_zero` functions
 //! from the `utils` module for its core operations, aligning with cryptographic best practices.
 //!
+//! The `BigUint` functions, which work modulo a given prime, produce typed `Share`s that record
+//! their dealing, so that shares of different secrets cannot be mixed by accident. Besides them,
+//! `generate_shares_over` and `reconstruct_secret_over` run the scheme over any `PrimeField`
+//! implementation on plain `(index, value)` tuples.
+//!
+//! `reconstruct_secret_robust` decodes the shares as a Reed–Solomon codeword instead: given `n`
+//! shares of a dealing with threshold `t`, it still recovers the secret when up to `(n - t) / 2` of
+//! them are corrupted, and reports which ones were. `check_consistency` only looks for cheaters:
+//! given more shares than the threshold, it checks that they lie on one polynomial and, if not,
+//! names the smallest sets of shares whose removal makes the rest consistent.
+//!
+//! The secret, the share values and the reconstructed secret are held in `Secret`s, and the
+//! sharing polynomial is wiped once the shares have been evaluated, so the secret does not linger
+//! in memory or show up in logs.
+//!
+//! With the `constant-time` feature, `generate_shares` and `reconstruct_secret` run their field
+//! arithmetic on the fixed-width `CtModPField` backend whenever the modulus is an odd number of at
+//! most 512 bits, so their timing does not depend on the secret or the shares. Larger moduli fall
+//! back to the variable-time `BigUint` arithmetic.
+//!
 
 use num_bigint::{BigUint, ToBigUint};
-use crate::utils::Polynomial;
-use crate::utils::lagrange_interpolation_zero;
+use rand::{thread_rng, CryptoRng, RngCore};
+use crate::algebra::PrimeField;
+use crate::error::{check_share_count, check_threshold, Result};
+use crate::secret::{zeroize_biguint, Secret};
+use crate::utils::{group_id, new_dealing_id_with_rng, Polynomial, Scheme, Share};
+use crate::utils::{berlekamp_welch_over, dealing_points, lagrange_interpolation_zero, lagrange_interpolation_zero_over};
+use crate::utils::{lagrange_interpolation_over, share_indices_over};
+use crate::algebra::ModPField;
+use crate::error::VsssError;
+use zeroize::Zeroizing;
+#[cfg(feature = "constant-time")]
+use crate::algebra::constant_time::{CtElement, CtModPField};
+#[cfg(feature = "constant-time")]
+use zeroize::Zeroize;
 
 /// Generates shares for Shamir's Secret Sharing scheme.
 ///
@@ -37,61 +69,499 @@ use crate::utils::lagrange_interpolation_zero;
 ///
 /// # Returns
 ///
-/// A vector of tuples representing the generated shares. Each tuple contains the x-coordinate
-/// (share index) and the corresponding y-coordinate (share value).
+/// A vector of the generated shares. Each share holds the x-coordinate (share index) and the
+/// corresponding y-coordinate (share value), tagged with the threshold, the modulus and a fresh
+/// dealing identifier. Fails with `VsssError::InvalidThreshold` if the threshold is zero or exceeds
+/// `num_shares`, with `VsssError::TooManyShares` if `num_shares` is not below the modulus, and with
+/// `VsssError::SecretOutOfRange` if the secret is not below the modulus.
 pub fn generate_shares(
-    secret: &BigUint,
+    secret: &Secret,
     threshold: usize,
     num_shares: usize,
     modulus: &BigUint,
-) -> Vec<(BigUint, BigUint)> {
-    let poly = Polynomial::new_for_shamir(threshold - 1, secret.bits() as usize, secret);
+) -> Result<Vec<Share>> {
+    generate_shares_with_rng(secret, threshold, num_shares, modulus, &mut thread_rng())
+}
+
+/// Generates shares for Shamir's Secret Sharing scheme as `generate_shares` does, drawing the
+/// polynomial coefficients and the dealing identifier from `rng`.
+///
+/// With a seeded RNG such as ChaCha20 the shares are reproducible, which is useful for test
+/// vectors and simulations; in production `rng` must be cryptographically secure and unseeded.
+pub fn generate_shares_with_rng<R: RngCore + CryptoRng>(
+    secret: &Secret,
+    threshold: usize,
+    num_shares: usize,
+    modulus: &BigUint,
+    rng: &mut R,
+) -> Result<Vec<Share>> {
+    check_threshold(threshold, num_shares)?;
+    check_share_count(num_shares, modulus)?;
+    #[cfg(feature = "constant-time")]
+    if let Ok(field) = CtModPField::new(modulus) {
+        return generate_shares_constant_time(&field, secret, threshold, num_shares, modulus, rng);
+    }
+    let poly = Zeroizing::new(Polynomial::new_for_shamir_mod_with_rng(threshold, secret.expose_secret(), modulus, rng)?);
+    let group_id = group_id(modulus);
+    let dealing_id = new_dealing_id_with_rng(rng);
     let mut shares = Vec::with_capacity(num_shares);
 
     for i in 1..=num_shares {
         let x = i.to_biguint().unwrap();
-        let y = poly.evaluate(&x) % modulus;
-        shares.push((x, y));
+        let y = Secret::new(poly.evaluate(&x) % modulus);
+        shares.push(Share::new(x, y, threshold, group_id, dealing_id, Scheme::Shamir));
     }
 
-    shares
+    Ok(shares)
+}
+
+/// Generates shares as `generate_shares_with_rng` does, with the arithmetic done in `field`.
+#[cfg(feature = "constant-time")]
+fn generate_shares_constant_time<R: RngCore + CryptoRng>(
+    field: &CtModPField,
+    secret: &Secret,
+    threshold: usize,
+    num_shares: usize,
+    modulus: &BigUint,
+    rng: &mut R,
+) -> Result<Vec<Share>> {
+    let mut constant = Option::from(field.from_biguint(secret.expose_secret())).ok_or(VsssError::SecretOutOfRange)?;
+    let mut poly = Polynomial::random_over_with_rng(field, &constant, threshold, rng);
+    let group_id = group_id(modulus);
+    let dealing_id = new_dealing_id_with_rng(rng);
+
+    let shares = (1..=num_shares as u64).map(|i| {
+        let y = Secret::new(field.to_biguint(&poly.evaluate_over(field, &field.element(i))));
+        Share::new(BigUint::from(i), y, threshold, group_id, dealing_id, Scheme::Shamir)
+    }).collect();
+
+    poly.coefficients.zeroize();
+    constant.zeroize();
+    Ok(shares)
+}
+
+/// Generates shares for Shamir's Secret Sharing scheme over an arbitrary field.
+///
+/// # Arguments
+///
+/// * `field` - The field the secret, coefficients and shares live in.
+/// * `secret` - The secret value to be shared.
+/// * `threshold` - The threshold value for reconstructing the secret.
+/// * `num_shares` - The number of shares to generate.
+///
+/// # Returns
+///
+/// A vector of `(index, value)` tuples, with indices `1..=num_shares` mapped into the field. Fails
+/// with `VsssError::InvalidThreshold` if the threshold is zero or exceeds `num_shares`, and with
+/// `VsssError::TooManyShares` if an index maps to zero or repeats in the field.
+pub fn generate_shares_over<F: PrimeField>(
+    field: &F,
+    secret: &F::Element,
+    threshold: usize,
+    num_shares: usize,
+) -> Result<Vec<(F::Element, F::Element)>> {
+    generate_shares_over_with_rng(field, secret, threshold, num_shares, &mut thread_rng())
+}
+
+/// Generates shares over an arbitrary field as `generate_shares_over` does, drawing the
+/// polynomial coefficients from `rng`.
+pub fn generate_shares_over_with_rng<F: PrimeField, R: RngCore + CryptoRng>(
+    field: &F,
+    secret: &F::Element,
+    threshold: usize,
+    num_shares: usize,
+    rng: &mut R,
+) -> Result<Vec<(F::Element, F::Element)>> {
+    check_threshold(threshold, num_shares)?;
+    let indices = share_indices_over(field, num_shares)?;
+    let mut poly = Polynomial::random_over_with_rng(field, secret, threshold, rng);
+
+    let shares = indices.into_iter().map(|x| {
+        let y = poly.evaluate_over(field, &x);
+        (x, y)
+    }).collect();
+
+    poly.zeroize_over(field);
+    Ok(shares)
 }
 
 /// Reconstructs the secret from shares using Lagrange interpolation.
 ///
 /// # Arguments
 ///
-/// * `shares` - A slice of tuples representing the shares. Each tuple contains the x-coordinate
-///   (share index) and the corresponding y-coordinate (share value).
+/// * `shares` - A slice of shares from a single dealing, at least as many as its threshold.
 /// * `modulus` - The modulus used for the finite field operations.
 ///
 /// # Returns
 ///
-/// The reconstructed secret if successful, otherwise None.
-pub fn reconstruct_secret(shares: &[(BigUint, BigUint)], modulus: &BigUint) -> Option<BigUint> {
-    lagrange_interpolation_zero(shares, modulus)
+/// The reconstructed secret if successful, wrapped in a `Secret`. Shares from different dealings or
+/// moduli, fewer shares than the threshold, and duplicate indices are reported as the matching
+/// `VsssError`.
+pub fn reconstruct_secret(shares: &[Share], modulus: &BigUint) -> Result<Secret> {
+    let mut points = dealing_points(shares, modulus)?;
+    #[cfg(feature = "constant-time")]
+    if let Ok(field) = CtModPField::new(modulus) {
+        let ct_points: Option<Vec<(CtElement, CtElement)>> = points.iter().map(|(x, y)| {
+            Option::from(field.from_biguint(x)).zip(Option::from(field.from_biguint(y)))
+        }).collect();
+        if let Some(mut ct_points) = ct_points {
+            points.iter_mut().for_each(|(_, value)| zeroize_biguint(value));
+            let secret = lagrange_interpolation_zero_over(&field, &ct_points).map(|s| Secret::new(field.to_biguint(&s)));
+            ct_points.iter_mut().for_each(|(_, value)| value.zeroize());
+            return secret;
+        }
+    }
+    let secret = lagrange_interpolation_zero(&points, modulus).map(Secret::new);
+    points.iter_mut().for_each(|(_, value)| zeroize_biguint(value));
+    secret
+}
+
+/// Reconstructs the secret from shares over an arbitrary field using Lagrange interpolation.
+///
+/// # Returns
+///
+/// The reconstructed secret if successful, otherwise `VsssError::DuplicateShareIndex` or
+/// `VsssError::InsufficientShares` if no shares are given.
+pub fn reconstruct_secret_over<F: PrimeField>(field: &F, shares: &[(F::Element, F::Element)]) -> Result<F::Element> {
+    lagrange_interpolation_zero_over(field, shares)
+}
+
+/// The result of `reconstruct_secret_robust`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RobustReconstruction {
+    pub secret: Secret, // The reconstructed secret
+    pub corrupted: Vec<BigUint>, // Indices of the shares that do not lie on the sharing polynomial
+}
+
+/// Reconstructs the secret from shares, correcting corrupted shares with Berlekamp–Welch decoding.
+///
+/// # Arguments
+///
+/// * `shares` - A slice of shares from a single dealing, at least as many as its threshold.
+/// * `modulus` - The modulus used for the finite field operations.
+///
+/// # Returns
+///
+/// The secret and the indices of the corrupted shares, of which there can be at most
+/// `(shares.len() - threshold) / 2`. Fails with `VsssError::InconsistentShares` if more shares were
+/// corrupted than can be corrected, and otherwise as `reconstruct_secret` does.
+pub fn reconstruct_secret_robust(shares: &[Share], modulus: &BigUint) -> Result<RobustReconstruction> {
+    let mut points = dealing_points(shares, modulus)?;
+    let decoded = berlekamp_welch_over(&ModPField::new(modulus.clone()), &points, shares[0].threshold);
+    points.iter_mut().for_each(|(_, value)| zeroize_biguint(value));
+    let (mut polynomial, corrupted) = decoded?;
+
+    let secret = Secret::new(polynomial.coefficients[0].clone());
+    polynomial.coefficients.iter_mut().for_each(zeroize_biguint);
+    let corrupted = corrupted.into_iter().map(|position| shares[position].index.clone()).collect();
+    Ok(RobustReconstruction { secret, corrupted })
+}
+
+/// Reconstructs the secret from shares over an arbitrary field, correcting corrupted shares with
+/// Berlekamp–Welch decoding.
+///
+/// # Returns
+///
+/// The secret and the positions in `shares` of the corrupted shares, or the errors of
+/// `utils::berlekamp_welch_over`.
+pub fn reconstruct_secret_robust_over<F: PrimeField>(
+    field: &F,
+    shares: &[(F::Element, F::Element)],
+    threshold: usize,
+) -> Result<(F::Element, Vec<usize>)> {
+    let (polynomial, corrupted) = berlekamp_welch_over(field, shares, threshold)?;
+    Ok((polynomial.coefficients[0].clone(), corrupted))
+}
+
+/// The largest number of shares for which `check_consistency` falls back to trying every set of
+/// suspects once there are too many inconsistent shares for Berlekamp–Welch decoding.
+pub const MAX_COMBINATORIAL_SHARES: usize = 16;
+
+/// The result of `check_consistency`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConsistencyReport {
+    pub consistent: bool, // Whether all shares lie on one polynomial of degree below the threshold
+    pub suspects: Vec<Vec<BigUint>>, // Every smallest set of share indices whose removal leaves the others consistent
+}
+
+/// Checks whether shares lie on a single polynomial of degree below their threshold and, if not,
+/// identifies the inconsistent shares.
+///
+/// Up to `(n - t) / 2` inconsistent shares among `n` shares of threshold `t` are found by
+/// Berlekamp–Welch decoding, and are then the only smallest explanation. Beyond that, and for at most
+/// `MAX_COMBINATORIAL_SHARES` shares, every set of suspects of increasing size is tried; several
+/// sets of the same size may then explain the inconsistency equally well, and all are reported.
+///
+/// # Arguments
+///
+/// * `shares` - A slice of shares from a single dealing, more than its threshold.
+/// * `modulus` - The modulus used for the finite field operations.
+///
+/// # Returns
+///
+/// The report, whose `suspects` are empty if the shares are consistent or if no set of suspects
+/// leaves more than `threshold` consistent shares. Fails with `VsssError::InsufficientShares` if
+/// there are no more shares than the threshold, and otherwise as `reconstruct_secret` does.
+pub fn check_consistency(shares: &[Share], modulus: &BigUint) -> Result<ConsistencyReport> {
+    let mut points = dealing_points(shares, modulus)?;
+    let found = find_inconsistent_shares_over(&ModPField::new(modulus.clone()), &points, shares[0].threshold);
+    points.iter_mut().for_each(|(_, value)| zeroize_biguint(value));
+    let found = found?;
+
+    let consistent = found == [Vec::<usize>::new()];
+    let suspects = if consistent {
+        Vec::new()
+    } else {
+        found.iter().map(|set| set.iter().map(|&position| shares[position].index.clone()).collect()).collect()
+    };
+    Ok(ConsistencyReport { consistent, suspects })
+}
+
+/// Finds the smallest sets of inconsistent shares over an arbitrary field, as `check_consistency`
+/// does.
+///
+/// # Returns
+///
+/// Every smallest set of positions in `shares` whose removal leaves the other shares on one
+/// polynomial of degree below `threshold`: a single empty set if the shares are consistent, and no
+/// set at all if the inconsistency cannot be explained. Fails with `VsssError::InsufficientShares`
+/// if there are no more shares than the threshold, or `VsssError::DuplicateShareIndex`.
+pub fn find_inconsistent_shares_over<F: PrimeField>(
+    field: &F,
+    shares: &[(F::Element, F::Element)],
+    threshold: usize,
+) -> Result<Vec<Vec<usize>>> {
+    if shares.len() <= threshold {
+        return Err(VsssError::InsufficientShares { required: threshold + 1, provided: shares.len() });
+    }
+    match berlekamp_welch_over(field, shares, threshold) {
+        Ok((_, corrupted)) => return Ok(vec![corrupted]),
+        Err(VsssError::InconsistentShares) => {}
+        Err(error) => return Err(error),
+    }
+    if shares.len() > MAX_COMBINATORIAL_SHARES {
+        return Ok(Vec::new());
+    }
+
+    // Fewer than (n - t) / 2 + 1 suspects would have been found by the decoder, and at least
+    // threshold + 1 shares must remain for their consistency to mean anything.
+    for size in (shares.len() - threshold) / 2 + 1..shares.len() - threshold {
+        let mut found = Vec::new();
+        let mut suspects: Vec<usize> = (0..size).collect();
+        loop {
+            let kept: Vec<_> = (0..shares.len()).filter(|i| !suspects.contains(i)).map(|i| shares[i].clone()).collect();
+            if lie_on_polynomial(field, &kept, threshold)? {
+                found.push(suspects.clone());
+            }
+            if !next_combination(&mut suspects, shares.len()) {
+                break;
+            }
+        }
+        if !found.is_empty() {
+            return Ok(found);
+        }
+    }
+    Ok(Vec::new())
+}
+
+/// Checks whether the points interpolated by their first `threshold` also pass through the others.
+fn lie_on_polynomial<F: PrimeField>(field: &F, points: &[(F::Element, F::Element)], threshold: usize) -> Result<bool> {
+    for (x, y) in &points[threshold..] {
+        if lagrange_interpolation_over(field, &points[..threshold], x)? != *y {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Advances `combination`, a strictly increasing list of positions below `n`, to the next one in
+/// lexicographic order, returning `false` once it was the last.
+fn next_combination(combination: &mut [usize], n: usize) -> bool {
+    let k = combination.len();
+    let Some(i) = (0..k).rev().find(|&i| combination[i] < n - k + i) else {
+        return false;
+    };
+    combination[i] += 1;
+    for j in i + 1..k {
+        combination[j] = combination[j - 1] + 1;
+    }
+    true
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use num_bigint::ToBigUint;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
 
     // Test for generating and reconstructing shares
     #[test]
     fn test_generate_and_reconstruct_shares() {
-        let secret = 87985.to_biguint().unwrap();
+        let secret = Secret::new(87985.to_biguint().unwrap());
         let threshold = 3;
         let num_shares = 5;
         let modulus = 678879987.to_biguint().unwrap();
 
         // Generate shares
-        let shares = generate_shares(&secret, threshold, num_shares, &modulus);
+        let shares = generate_shares(&secret, threshold, num_shares, &modulus).unwrap();
 
         // Reconstruct secret
         let reconstructed_secret = reconstruct_secret(&shares[..threshold], &modulus).unwrap();
 
         // Assert equality
         assert_eq!(secret, reconstructed_secret);
+
+        // The polynomial has degree threshold - 1, so fewer points do not determine the secret
+        let points: Vec<(BigUint, BigUint)> = shares[..threshold - 1].iter().map(Share::point).collect();
+        assert_ne!(&lagrange_interpolation_zero(&points, &modulus).unwrap(), secret.expose_secret());
+    }
+
+    // Test that shares from different dealings are not combined
+    #[test]
+    fn test_rejects_mixed_dealings() {
+        let modulus = 678879987.to_biguint().unwrap();
+        let first = generate_shares(&Secret::new(1111.to_biguint().unwrap()), 2, 3, &modulus).unwrap();
+        let second = generate_shares(&Secret::new(2222.to_biguint().unwrap()), 2, 3, &modulus).unwrap();
+
+        let mixed = vec![first[0].clone(), second[1].clone()];
+        assert_eq!(reconstruct_secret(&mixed, &modulus), Err(VsssError::MixedDealings));
+        assert_eq!(
+            reconstruct_secret(&first[..1], &modulus),
+            Err(VsssError::InsufficientShares { required: 2, provided: 1 })
+        );
+        assert_eq!(reconstruct_secret(&first[..2], &7919.to_biguint().unwrap()), Err(VsssError::GroupMismatch));
+        let duplicated = vec![first[0].clone(), first[0].clone()];
+        assert_eq!(reconstruct_secret(&duplicated, &modulus), Err(VsssError::DuplicateShareIndex));
+    }
+
+    // Test that a seeded RNG produces reproducible shares
+    #[test]
+    fn test_generate_shares_with_seeded_rng() {
+        let modulus = 678879987.to_biguint().unwrap();
+        let secret = Secret::new(87985.to_biguint().unwrap());
+        let first = generate_shares_with_rng(&secret, 3, 5, &modulus, &mut ChaCha20Rng::seed_from_u64(42)).unwrap();
+        let second = generate_shares_with_rng(&secret, 3, 5, &modulus, &mut ChaCha20Rng::seed_from_u64(42)).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(reconstruct_secret(&first[2..], &modulus).unwrap(), secret);
+        assert!(!format!("{:?}", first).contains("87985"));
+    }
+
+    // Test that the constant-time backend agrees with the BigUint arithmetic
+    #[cfg(feature = "constant-time")]
+    #[test]
+    fn test_constant_time_backend() {
+        let modulus = 678879987.to_biguint().unwrap();
+        let secret = Secret::new(87985.to_biguint().unwrap());
+        let shares = generate_shares(&secret, 3, 5, &modulus).unwrap();
+
+        let points: Vec<(BigUint, BigUint)> = shares.iter().map(Share::point).collect();
+        let field = ModPField::new(modulus.clone());
+        assert_eq!(&reconstruct_secret_over(&field, &points[1..4]).unwrap(), secret.expose_secret());
+        assert_eq!(reconstruct_secret(&shares[2..], &modulus).unwrap(), secret);
+
+        // A modulus wider than 512 bits falls back to the BigUint arithmetic.
+        let wide = crate::utils::StandardGroup::Modp2048.q();
+        let shares = generate_shares(&secret, 3, 5, &wide).unwrap();
+        assert_eq!(reconstruct_secret(&shares[..3], &wide).unwrap(), secret);
     }
-}
\ No newline at end of file
+
+    // Test that invalid sharing parameters are reported instead of panicking
+    #[test]
+    fn test_rejects_invalid_parameters() {
+        let modulus = 7919.to_biguint().unwrap();
+        let secret = Secret::new(1234.to_biguint().unwrap());
+        assert_eq!(
+            generate_shares(&secret, 0, 5, &modulus),
+            Err(VsssError::InvalidThreshold { threshold: 0, num_shares: 5 })
+        );
+        assert_eq!(
+            generate_shares(&secret, 6, 5, &modulus),
+            Err(VsssError::InvalidThreshold { threshold: 6, num_shares: 5 })
+        );
+        assert_eq!(generate_shares(&Secret::new(12345.to_biguint().unwrap()), 3, 5, &modulus), Err(VsssError::SecretOutOfRange));
+
+        // The share at index 7 modulo 7 would be the secret itself.
+        let small = 7.to_biguint().unwrap();
+        let secret = Secret::new(3.to_biguint().unwrap());
+        assert_eq!(generate_shares(&secret, 2, 6, &small).unwrap().len(), 6);
+        assert_eq!(generate_shares(&secret, 2, 7, &small), Err(VsssError::TooManyShares { num_shares: 7, max: 6 }));
+        let field = ModPField::new(small);
+        assert_eq!(
+            generate_shares_over(&field, secret.expose_secret(), 2, 7),
+            Err(VsssError::TooManyShares { num_shares: 7, max: 6 })
+        );
+        assert_eq!(
+            generate_shares_over(&crate::gf256::Gf256::table(), &42, 2, 300),
+            Err(VsssError::TooManyShares { num_shares: 300, max: 255 })
+        );
+    }
+
+    // Test for generating and reconstructing shares over a generic field
+    #[test]
+    fn test_generate_and_reconstruct_shares_over_field() {
+        let field = ModPField::new(678880003.to_biguint().unwrap());
+        let secret = 87985.to_biguint().unwrap();
+        let shares = generate_shares_over(&field, &secret, 3, 5).unwrap();
+
+        assert_eq!(reconstruct_secret_over(&field, &shares[2..]).unwrap(), secret);
+        assert_ne!(reconstruct_secret_over(&field, &shares[..2]).unwrap(), secret);
+    }
+
+    // Test that corrupted shares are corrected and reported
+    #[test]
+    fn test_reconstruct_secret_robust() {
+        let modulus = 678880003.to_biguint().unwrap();
+        let secret = Secret::new(87985.to_biguint().unwrap());
+        let mut shares = generate_shares(&secret, 3, 8, &modulus).unwrap();
+
+        let clean = reconstruct_secret_robust(&shares, &modulus).unwrap();
+        assert_eq!(clean, RobustReconstruction { secret: reconstruct_secret(&shares[..3], &modulus).unwrap(), corrupted: vec![] });
+
+        shares[2].value = Secret::new(shares[2].value.expose_secret() + 1u32);
+        shares[5].value = Secret::new(12345.to_biguint().unwrap());
+        assert_ne!(reconstruct_secret(&shares[..3], &modulus).unwrap(), secret);
+        let robust = reconstruct_secret_robust(&shares, &modulus).unwrap();
+        assert_eq!(robust.secret, secret);
+        assert_eq!(robust.corrupted, vec![3.to_biguint().unwrap(), 6.to_biguint().unwrap()]);
+
+        // Six shares of a threshold-3 dealing correct a single error.
+        assert_eq!(reconstruct_secret_robust(&shares[2..], &modulus), Err(VsssError::InconsistentShares));
+
+        let field = ModPField::new(modulus.clone());
+        let points: Vec<(BigUint, BigUint)> = shares.iter().map(Share::point).collect();
+        assert_eq!(reconstruct_secret_robust_over(&field, &points, 3).unwrap(), (secret.expose_secret().clone(), vec![2, 5]));
+    }
+
+    // Test that inconsistent shares are identified, by decoding or by trying every set of suspects
+    #[test]
+    fn test_check_consistency() {
+        let modulus = 678880003.to_biguint().unwrap();
+        let secret = Secret::new(87985.to_biguint().unwrap());
+        let mut shares = generate_shares(&secret, 3, 6, &modulus).unwrap();
+        assert_eq!(check_consistency(&shares, &modulus).unwrap(), ConsistencyReport { consistent: true, suspects: vec![] });
+        assert_eq!(
+            check_consistency(&shares[..3], &modulus),
+            Err(VsssError::InsufficientShares { required: 4, provided: 3 })
+        );
+
+        // One bad share is within the decoding capacity of six shares, two are found by enumeration.
+        shares[1].value = Secret::new(shares[1].value.expose_secret() + 1u32);
+        assert_eq!(check_consistency(&shares, &modulus).unwrap().suspects, vec![vec![2.to_biguint().unwrap()]]);
+        shares[4].value = Secret::new(4242.to_biguint().unwrap());
+        let report = check_consistency(&shares, &modulus).unwrap();
+        assert!(!report.consistent);
+        assert_eq!(report.suspects, vec![vec![2.to_biguint().unwrap(), 5.to_biguint().unwrap()]]);
+    }
+
+    // Test that every smallest explanation is reported when the cheaters are indistinguishable
+    #[test]
+    fn test_find_inconsistent_shares_ambiguous() {
+        let field = ModPField::new(7919.to_biguint().unwrap());
+        // Shares 1-3 lie on y = x + 1, and shares 3-5 on y = 2x - 2.
+        let points: Vec<(BigUint, BigUint)> = [(1u64, 2u64), (2, 3), (3, 4), (4, 6), (5, 8)]
+            .iter().map(|&(x, y)| (field.element(x), field.element(y))).collect();
+        assert_eq!(find_inconsistent_shares_over(&field, &points, 2).unwrap(), vec![vec![0, 1], vec![3, 4]]);
+        assert_eq!(find_inconsistent_shares_over(&field, &points[..3], 2).unwrap(), vec![Vec::<usize>::new()]);
+    }
+}
@@ -0,0 +1,646 @@
+//! # SLIP-39 Module
+//!
+//! This module implements SLIP-39, the mnemonic secret sharing scheme understood by hardware
+//! wallets such as Trezor. A master secret of at least 16 bytes is first encrypted with a
+//! passphrase by a four-round Feistel network built on PBKDF2-HMAC-SHA256, and the encrypted
+//! secret is then shared in two levels: it is split into group shares with a group threshold, and
+//! each group share is split again into member shares with the threshold of its group. A secret
+//! can for example be recovered from any 2 of 3 shares held by the owner, or from 3 of 5 shares
+//! held by friends.
+//!
+//! Sharing uses the GF(2^8) field of the `gf256` module, with its constant-time arithmetic. Every
+//! share is written as a mnemonic of 20 or more words from a fixed list of 1024 words, carrying
+//! the metadata of its dealing and an RS1024 checksum that detects any three mistyped words.
+//!
+//! Mnemonics created with the `extendable` flag, which new dealings should use, can later be
+//! complemented with further groups sharing the same encrypted master secret.
+
+use crate::algebra::PrimeField;
+use crate::error::{check_threshold, Result, VsssError};
+use crate::gf256::Gf256;
+use crate::utils::lagrange_interpolation_over;
+use hmac::{Hmac, Mac, NewMac};
+use rand::{thread_rng, CryptoRng, Rng, RngCore};
+use sha2::Sha256;
+use std::fmt;
+use zeroize::Zeroizing;
+
+/// The 1024 words of the SLIP-39 word list, in order.
+const WORDLIST: &str = include_str!("wordlist.txt");
+
+/// The number of bits encoded by each word.
+const RADIX_BITS: u32 = 10;
+
+/// The largest number of groups, and of members in a group.
+pub const MAX_SHARE_COUNT: u8 = 16;
+
+/// The largest iteration exponent.
+pub const MAX_ITERATION_EXPONENT: u8 = 15;
+
+/// The shortest master secret, in bytes.
+pub const MIN_SECRET_LEN: usize = 16;
+
+/// The number of words of the checksum.
+const CHECKSUM_WORDS: usize = 3;
+
+/// The number of words of the metadata before the share value.
+const PREFIX_WORDS: usize = 4;
+
+/// The number of words of a mnemonic besides the share value.
+const METADATA_WORDS: usize = PREFIX_WORDS + CHECKSUM_WORDS;
+
+/// The length of the digest that protects the shared secret.
+const DIGEST_LEN: usize = 4;
+
+/// The x-coordinate of the shared secret.
+const SECRET_INDEX: u8 = 255;
+
+/// The x-coordinate of the digest share.
+const DIGEST_INDEX: u8 = 254;
+
+/// The PBKDF2 iteration count of the encryption, for an iteration exponent of zero.
+const BASE_ITERATION_COUNT: u32 = 10000;
+
+/// The number of rounds of the Feistel network.
+const ROUND_COUNT: u8 = 4;
+
+/// The customization string of non-extendable mnemonics.
+const CUSTOMIZATION_ORIGINAL: &[u8] = b"shamir";
+
+/// The customization string of extendable mnemonics.
+const CUSTOMIZATION_EXTENDABLE: &[u8] = b"shamir_extendable";
+
+/// The generator of the RS1024 checksum.
+const GENERATOR: [u32; 10] = [
+    0xE0E040, 0x1C1C080, 0x3838100, 0x7070200, 0xE0E0009,
+    0x1C0C2412, 0x38086C24, 0x3090FC48, 0x21B1F890, 0x3F3F120,
+];
+
+/// A single SLIP-39 share, as encoded in a mnemonic. Its value is wiped on drop and redacted
+/// when the share is formatted.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Slip39Share {
+    pub identifier: u16, // Random 15-bit identifier shared by all shares of a dealing
+    pub extendable: bool, // Whether further groups can be added to the dealing later
+    pub iteration_exponent: u8, // The encryption uses 10000 * 2^iteration_exponent PBKDF2 iterations
+    pub group_index: u8, // Index of the group the share belongs to
+    pub group_threshold: u8, // Number of groups required to recover the secret
+    pub group_count: u8, // Total number of groups
+    pub member_index: u8, // Index of the share within its group
+    pub member_threshold: u8, // Number of shares of the group required to recover its group share
+    pub value: Zeroizing<Vec<u8>>, // The share value, as long as the master secret
+}
+
+impl Slip39Share {
+    /// Encodes the share as a mnemonic of space-separated words.
+    ///
+    /// # Returns
+    ///
+    /// The mnemonic, or `VsssError::InvalidEncoding` if a field is out of range for its width,
+    /// for example an identifier of more than 15 bits.
+    pub fn to_mnemonic(&self) -> Result<String> {
+        let in_range = self.identifier < 1 << 15
+            && self.iteration_exponent <= MAX_ITERATION_EXPONENT
+            && [self.group_index, self.member_index].iter().all(|&i| i < MAX_SHARE_COUNT)
+            && [self.group_threshold, self.group_count, self.member_threshold].iter().all(|&n| (1..=MAX_SHARE_COUNT).contains(&n));
+        if !in_range || self.value.len() < MIN_SECRET_LEN || !self.value.len().is_multiple_of(2) {
+            return Err(VsssError::InvalidEncoding);
+        }
+
+        let mut bits = BitWriter::default();
+        bits.push(self.identifier as u32, 15);
+        bits.push(self.extendable as u32, 1);
+        bits.push(self.iteration_exponent as u32, 4);
+        bits.push(self.group_index as u32, 4);
+        bits.push(self.group_threshold as u32 - 1, 4);
+        bits.push(self.group_count as u32 - 1, 4);
+        bits.push(self.member_index as u32, 4);
+        bits.push(self.member_threshold as u32 - 1, 4);
+        // The value is left-padded with zero bits to a whole number of words.
+        let value_bits = 8 * self.value.len() as u32;
+        bits.push(0, (RADIX_BITS - value_bits % RADIX_BITS) % RADIX_BITS);
+        for &byte in self.value.iter() {
+            bits.push(byte as u32, 8);
+        }
+
+        let mut indices = bits.words;
+        let checksum = rs1024_checksum(&indices, self.extendable);
+        indices.extend_from_slice(&checksum);
+        let words = wordlist();
+        Ok(indices.iter().map(|&i| words[i as usize]).collect::<Vec<_>>().join(" "))
+    }
+
+    /// Decodes a share from a mnemonic. Words are matched case-insensitively and may be separated
+    /// by any whitespace.
+    ///
+    /// # Returns
+    ///
+    /// The share, `VsssError::CorruptedText` with the position of the first word that is not in
+    /// the word list, or without a position if the checksum does not match, and
+    /// `VsssError::InvalidEncoding` if the mnemonic is too short or its padding is invalid.
+    pub fn from_mnemonic(mnemonic: &str) -> Result<Self> {
+        let words = wordlist();
+        let indices = Zeroizing::new(mnemonic
+            .split_whitespace()
+            .enumerate()
+            .map(|(position, word)| {
+                words
+                    .binary_search(&word.to_lowercase().as_str())
+                    .map(|index| index as u16)
+                    .map_err(|_| VsssError::CorruptedText { position: Some(position + 1) })
+            })
+            .collect::<Result<Vec<u16>>>()?);
+
+        let value_words = indices.len().checked_sub(METADATA_WORDS).ok_or(VsssError::InvalidEncoding)?;
+        let padding = (RADIX_BITS as usize * value_words) % 16;
+        if 8 * MIN_SECRET_LEN + padding > RADIX_BITS as usize * value_words || padding > 8 {
+            return Err(VsssError::InvalidEncoding);
+        }
+        let extendable = (indices[1] >> 4) & 1 == 1;
+        if !rs1024_verify(&indices, extendable) {
+            return Err(VsssError::CorruptedText { position: None });
+        }
+
+        let mut bits = BitReader::new(&indices[..indices.len() - CHECKSUM_WORDS]);
+        let identifier = bits.read(15) as u16;
+        bits.read(1);
+        let iteration_exponent = bits.read(4) as u8;
+        let group_index = bits.read(4) as u8;
+        let group_threshold = bits.read(4) as u8 + 1;
+        let group_count = bits.read(4) as u8 + 1;
+        let member_index = bits.read(4) as u8;
+        let member_threshold = bits.read(4) as u8 + 1;
+        if bits.read(padding as u32) != 0 {
+            return Err(VsssError::InvalidEncoding);
+        }
+        let value = Zeroizing::new((0..(RADIX_BITS as usize * value_words - padding) / 8).map(|_| bits.read(8) as u8).collect());
+        if group_threshold > group_count {
+            return Err(VsssError::InvalidEncoding);
+        }
+
+        Ok(Slip39Share {
+            identifier,
+            extendable,
+            iteration_exponent,
+            group_index,
+            group_threshold,
+            group_count,
+            member_index,
+            member_threshold,
+            value,
+        })
+    }
+}
+
+impl fmt::Debug for Slip39Share {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Slip39Share")
+            .field("identifier", &self.identifier)
+            .field("extendable", &self.extendable)
+            .field("iteration_exponent", &self.iteration_exponent)
+            .field("group_index", &self.group_index)
+            .field("group_threshold", &self.group_threshold)
+            .field("group_count", &self.group_count)
+            .field("member_index", &self.member_index)
+            .field("member_threshold", &self.member_threshold)
+            .field("value", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Splits a master secret into SLIP-39 mnemonics.
+///
+/// # Arguments
+///
+/// * `group_threshold` - The number of groups required to recover the secret.
+/// * `groups` - The `(member_threshold, member_count)` of each group. A group with a member
+///   threshold of one must have a single member.
+/// * `master_secret` - The secret, at least 16 bytes long and of even length.
+/// * `passphrase` - The passphrase the secret is encrypted with, made of printable ASCII
+///   characters; may be empty.
+/// * `extendable` - Whether groups can be added to the dealing later.
+/// * `iteration_exponent` - The encryption uses `10000 * 2^iteration_exponent` PBKDF2 iterations.
+///
+/// # Returns
+///
+/// The mnemonics of each group. Fails with `VsssError::InvalidThreshold` if a threshold is zero,
+/// exceeds its count, or is one for a group of several members, with `VsssError::TooManyShares`
+/// if there are more than 16 groups or members in a group, and with `VsssError::InvalidEncoding`
+/// if the secret, the passphrase or the iteration exponent is not valid.
+pub fn generate_mnemonics(
+    group_threshold: u8,
+    groups: &[(u8, u8)],
+    master_secret: &[u8],
+    passphrase: &str,
+    extendable: bool,
+    iteration_exponent: u8,
+) -> Result<Vec<Vec<String>>> {
+    generate_mnemonics_with_rng(group_threshold, groups, master_secret, passphrase, extendable, iteration_exponent, &mut thread_rng())
+}
+
+/// Splits a master secret into SLIP-39 mnemonics as `generate_mnemonics` does, drawing the
+/// identifier and the random share values from `rng`.
+pub fn generate_mnemonics_with_rng<R: RngCore + CryptoRng>(
+    group_threshold: u8,
+    groups: &[(u8, u8)],
+    master_secret: &[u8],
+    passphrase: &str,
+    extendable: bool,
+    iteration_exponent: u8,
+    rng: &mut R,
+) -> Result<Vec<Vec<String>>> {
+    if master_secret.len() < MIN_SECRET_LEN || !master_secret.len().is_multiple_of(2) || iteration_exponent > MAX_ITERATION_EXPONENT {
+        return Err(VsssError::InvalidEncoding);
+    }
+    if groups.len() > MAX_SHARE_COUNT as usize {
+        return Err(VsssError::TooManyShares { num_shares: groups.len(), max: MAX_SHARE_COUNT as usize });
+    }
+    check_threshold(group_threshold as usize, groups.len())?;
+    for &(member_threshold, member_count) in groups {
+        if member_count > MAX_SHARE_COUNT {
+            return Err(VsssError::TooManyShares { num_shares: member_count as usize, max: MAX_SHARE_COUNT as usize });
+        }
+        check_threshold(member_threshold as usize, member_count as usize)?;
+        if member_threshold == 1 && member_count > 1 {
+            return Err(VsssError::InvalidThreshold { threshold: 1, num_shares: member_count as usize });
+        }
+    }
+
+    let identifier = rng.gen_range(0..1 << 15);
+    let encrypted = encrypt(master_secret, passphrase, iteration_exponent, identifier, extendable)?;
+    let group_shares = split_secret(group_threshold, groups.len() as u8, &encrypted, rng)?;
+
+    groups.iter().zip(group_shares.iter()).map(|(&(member_threshold, member_count), (group_index, group_secret))| {
+        split_secret(member_threshold, member_count, group_secret, rng)?
+            .into_iter()
+            .map(|(member_index, value)| Slip39Share {
+                identifier,
+                extendable,
+                iteration_exponent,
+                group_index: *group_index,
+                group_threshold,
+                group_count: groups.len() as u8,
+                member_index,
+                member_threshold,
+                value,
+            }.to_mnemonic())
+            .collect()
+    }).collect()
+}
+
+/// Recovers the master secret from SLIP-39 mnemonics.
+///
+/// # Arguments
+///
+/// * `mnemonics` - Mnemonics of at least `group_threshold` groups, with at least the member
+///   threshold of mnemonics from each of them. Extra groups and mnemonics are ignored.
+/// * `passphrase` - The passphrase the secret was encrypted with. A wrong passphrase yields a
+///   different secret rather than an error, as SLIP-39 intends.
+///
+/// # Returns
+///
+/// The master secret. Fails with the errors of `Slip39Share::from_mnemonic`, with
+/// `VsssError::MixedDealings` if the mnemonics belong to different dealings or disagree on the
+/// thresholds, `VsssError::DuplicateShareIndex` if two different mnemonics have the same index,
+/// `VsssError::InsufficientShares` if fewer than `group_threshold` groups are complete, and
+/// `VsssError::InconsistentShares` if the recovered secret does not match its digest.
+pub fn combine_mnemonics<S: AsRef<str>>(mnemonics: &[S], passphrase: &str) -> Result<Zeroizing<Vec<u8>>> {
+    let shares = mnemonics.iter().map(|m| Slip39Share::from_mnemonic(m.as_ref())).collect::<Result<Vec<_>>>()?;
+    let first = shares.first().ok_or(VsssError::InsufficientShares { required: 1, provided: 0 })?;
+    let same_dealing = |s: &Slip39Share| {
+        (s.identifier, s.extendable, s.iteration_exponent, s.group_threshold, s.group_count, s.value.len())
+            == (first.identifier, first.extendable, first.iteration_exponent, first.group_threshold, first.group_count, first.value.len())
+    };
+    if !shares.iter().all(|s| same_dealing(s) && s.group_index < s.group_count) {
+        return Err(VsssError::MixedDealings);
+    }
+
+    let mut group_shares = Vec::new();
+    for group_index in 0..first.group_count {
+        let mut members: Vec<&Slip39Share> = Vec::new();
+        for share in shares.iter().filter(|s| s.group_index == group_index) {
+            if share.member_threshold != members.first().map_or(share.member_threshold, |m| m.member_threshold) {
+                return Err(VsssError::MixedDealings);
+            }
+            match members.iter().find(|m| m.member_index == share.member_index) {
+                Some(member) if member.value == share.value => continue,
+                Some(_) => return Err(VsssError::DuplicateShareIndex),
+                None => members.push(share),
+            }
+        }
+        let Some(member) = members.first() else { continue };
+        if members.len() >= member.member_threshold as usize && group_shares.len() < first.group_threshold as usize {
+            let points: Vec<(u8, &[u8])> = members[..member.member_threshold as usize]
+                .iter()
+                .map(|m| (m.member_index, m.value.as_slice()))
+                .collect();
+            group_shares.push((group_index, recover_secret(member.member_threshold, &points)?));
+        }
+    }
+    if group_shares.len() < first.group_threshold as usize {
+        return Err(VsssError::InsufficientShares { required: first.group_threshold as usize, provided: group_shares.len() });
+    }
+
+    let points: Vec<(u8, &[u8])> = group_shares.iter().map(|(index, value)| (*index, value.as_slice())).collect();
+    let encrypted = recover_secret(first.group_threshold, &points)?;
+    decrypt(&encrypted, passphrase, first.iteration_exponent, first.identifier, first.extendable)
+}
+
+/// Returns the word list.
+fn wordlist() -> Vec<&'static str> {
+    WORDLIST.lines().collect()
+}
+
+/// Computes the RS1024 checksum state of `values`.
+fn rs1024_polymod(values: impl IntoIterator<Item = u32>) -> u32 {
+    let mut checksum = 1u32;
+    for value in values {
+        let top = checksum >> 20;
+        checksum = ((checksum & 0xFFFFF) << 10) ^ value;
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                checksum ^= generator;
+            }
+        }
+    }
+    checksum
+}
+
+/// Returns the customization string the checksum of a mnemonic is keyed with.
+fn customization(extendable: bool) -> &'static [u8] {
+    if extendable {
+        CUSTOMIZATION_EXTENDABLE
+    } else {
+        CUSTOMIZATION_ORIGINAL
+    }
+}
+
+/// Computes the three checksum words of a mnemonic.
+fn rs1024_checksum(indices: &[u16], extendable: bool) -> [u16; CHECKSUM_WORDS] {
+    let values = customization(extendable).iter().map(|&c| c as u32)
+        .chain(indices.iter().map(|&i| i as u32))
+        .chain([0; CHECKSUM_WORDS]);
+    let polymod = rs1024_polymod(values) ^ 1;
+    [(polymod >> 20) as u16 & 1023, (polymod >> 10) as u16 & 1023, polymod as u16 & 1023]
+}
+
+/// Checks the checksum words at the end of a mnemonic.
+fn rs1024_verify(indices: &[u16], extendable: bool) -> bool {
+    let values = customization(extendable).iter().map(|&c| c as u32).chain(indices.iter().map(|&i| i as u32));
+    rs1024_polymod(values) == 1
+}
+
+/// Computes the digest of a shared secret, keyed with the random part of the digest share.
+fn digest(random_part: &[u8], secret: &[u8]) -> [u8; DIGEST_LEN] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(random_part).expect("HMAC accepts keys of any length");
+    mac.update(secret);
+    let mut digest = [0u8; DIGEST_LEN];
+    digest.copy_from_slice(&mac.finalize().into_bytes()[..DIGEST_LEN]);
+    digest
+}
+
+/// Evaluates at `x` the polynomials over GF(2^8) passing through `points`, one byte at a time.
+fn interpolate(points: &[(u8, &[u8])], x: u8) -> Result<Zeroizing<Vec<u8>>> {
+    let field = Gf256::constant_time();
+    let len = points.first().ok_or(VsssError::InsufficientShares { required: 1, provided: 0 })?.1.len();
+    let mut column = Zeroizing::new(points.iter().map(|(index, _)| (*index, 0u8)).collect::<Vec<_>>());
+    (0..len).map(|position| {
+        for (point, (_, value)) in column.iter_mut().zip(points) {
+            point.1 = value[position];
+        }
+        lagrange_interpolation_over(&field, &column, &field.element(x as u64))
+    }).collect::<Result<Vec<u8>>>().map(Zeroizing::new)
+}
+
+/// Splits `secret` into `count` shares with x-coordinates `0..count`, any `threshold` of which
+/// recover it. For thresholds above one, a digest share at `DIGEST_INDEX` lets the recovered
+/// secret be checked.
+fn split_secret<R: RngCore + CryptoRng>(threshold: u8, count: u8, secret: &[u8], rng: &mut R) -> Result<Vec<(u8, Zeroizing<Vec<u8>>)>> {
+    if threshold == 1 {
+        return Ok((0..count).map(|index| (index, Zeroizing::new(secret.to_vec()))).collect());
+    }
+    let random_value = |rng: &mut R| {
+        let mut value = Zeroizing::new(vec![0u8; secret.len()]);
+        rng.fill_bytes(&mut value);
+        value
+    };
+
+    let mut shares: Vec<(u8, Zeroizing<Vec<u8>>)> = (0..threshold - 2).map(|index| (index, random_value(rng))).collect();
+    let mut digest_share = random_value(rng);
+    let tag = digest(&digest_share[DIGEST_LEN..], secret);
+    digest_share[..DIGEST_LEN].copy_from_slice(&tag);
+
+    let base: Vec<(u8, &[u8])> = shares.iter()
+        .map(|(index, value)| (*index, value.as_slice()))
+        .chain([(DIGEST_INDEX, digest_share.as_slice()), (SECRET_INDEX, secret)])
+        .collect();
+    let derived = (threshold - 2..count).map(|index| Ok((index, interpolate(&base, index)?))).collect::<Result<Vec<_>>>()?;
+    shares.extend(derived);
+    Ok(shares)
+}
+
+/// Recovers a secret split by `split_secret` from `threshold` shares, checking its digest.
+fn recover_secret(threshold: u8, points: &[(u8, &[u8])]) -> Result<Zeroizing<Vec<u8>>> {
+    if threshold == 1 {
+        return Ok(Zeroizing::new(points[0].1.to_vec()));
+    }
+    let secret = interpolate(points, SECRET_INDEX)?;
+    let digest_share = interpolate(points, DIGEST_INDEX)?;
+    if digest(&digest_share[DIGEST_LEN..], &secret) != digest_share[..DIGEST_LEN] {
+        return Err(VsssError::InconsistentShares);
+    }
+    Ok(secret)
+}
+
+/// Runs the Feistel network over `input` with the given round order.
+fn feistel(
+    input: &[u8],
+    passphrase: &str,
+    iteration_exponent: u8,
+    identifier: u16,
+    extendable: bool,
+    rounds: impl Iterator<Item = u8>,
+) -> Result<Zeroizing<Vec<u8>>> {
+    if !passphrase.bytes().all(|b| (32..=126).contains(&b)) || !input.len().is_multiple_of(2) {
+        return Err(VsssError::InvalidEncoding);
+    }
+    let mut salt = Vec::new();
+    if !extendable {
+        salt.extend_from_slice(CUSTOMIZATION_ORIGINAL);
+        salt.extend_from_slice(&identifier.to_be_bytes());
+    }
+    let iterations = (BASE_ITERATION_COUNT << iteration_exponent) / ROUND_COUNT as u32;
+
+    let half = input.len() / 2;
+    let mut left = Zeroizing::new(input[..half].to_vec());
+    let mut right = Zeroizing::new(input[half..].to_vec());
+    for round in rounds {
+        let mut password = Zeroizing::new(vec![round]);
+        password.extend_from_slice(passphrase.as_bytes());
+        let mut round_salt = Zeroizing::new(salt.clone());
+        round_salt.extend_from_slice(&right);
+        let mut f = Zeroizing::new(vec![0u8; half]);
+        pbkdf2::pbkdf2::<Hmac<Sha256>>(&password, &round_salt, iterations, &mut f);
+        for (l, f) in left.iter_mut().zip(f.iter()) {
+            *l ^= f;
+        }
+        std::mem::swap(&mut left, &mut right);
+    }
+    let mut output = Zeroizing::new(right.to_vec());
+    output.extend_from_slice(&left);
+    Ok(output)
+}
+
+/// Encrypts a master secret with a passphrase.
+fn encrypt(secret: &[u8], passphrase: &str, iteration_exponent: u8, identifier: u16, extendable: bool) -> Result<Zeroizing<Vec<u8>>> {
+    feistel(secret, passphrase, iteration_exponent, identifier, extendable, 0..ROUND_COUNT)
+}
+
+/// Decrypts an encrypted master secret with a passphrase.
+fn decrypt(encrypted: &[u8], passphrase: &str, iteration_exponent: u8, identifier: u16, extendable: bool) -> Result<Zeroizing<Vec<u8>>> {
+    feistel(encrypted, passphrase, iteration_exponent, identifier, extendable, (0..ROUND_COUNT).rev())
+}
+
+/// Packs fields of up to 32 bits into 10-bit words.
+#[derive(Default)]
+struct BitWriter {
+    words: Vec<u16>, // Completed words
+    buffer: u64, // Bits not yet forming a complete word
+    len: u32, // Number of bits in the buffer
+}
+
+impl BitWriter {
+    fn push(&mut self, value: u32, bits: u32) {
+        self.buffer = (self.buffer << bits) | value as u64;
+        self.len += bits;
+        while self.len >= RADIX_BITS {
+            self.len -= RADIX_BITS;
+            self.words.push((self.buffer >> self.len) as u16 & 1023);
+        }
+        self.buffer &= (1 << self.len) - 1;
+    }
+}
+
+/// Reads fields of up to 32 bits from 10-bit words.
+struct BitReader<'a> {
+    words: std::slice::Iter<'a, u16>, // Words not yet read
+    buffer: u64, // Bits read from the words but not yet returned
+    len: u32, // Number of bits in the buffer
+}
+
+impl<'a> BitReader<'a> {
+    fn new(words: &'a [u16]) -> Self {
+        BitReader { words: words.iter(), buffer: 0, len: 0 }
+    }
+
+    /// Reads the next `bits` bits, or zeros past the end of the words.
+    fn read(&mut self, bits: u32) -> u32 {
+        while self.len < bits {
+            self.buffer = (self.buffer << RADIX_BITS) | *self.words.next().unwrap_or(&0) as u64;
+            self.len += RADIX_BITS;
+        }
+        self.len -= bits;
+        let value = (self.buffer >> self.len) as u32 & ((1u64 << bits) - 1) as u32;
+        self.buffer &= (1 << self.len) - 1;
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn test_wordlist() {
+        let words = wordlist();
+        assert_eq!(words.len(), 1024);
+        assert!(words.windows(2).all(|pair| pair[0][..4] < pair[1][..4]));
+        assert_eq!((words[0], words[1023]), ("academic", "zero"));
+    }
+
+    #[test]
+    fn test_spec_vectors() {
+        // Test vectors 1, 4, 17 and 21 of the SLIP-39 specification, with the passphrase "TREZOR".
+        let vectors: [(&[&str], &str); 4] = [
+            (
+                &["duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision keyboard"],
+                "bb54aac4b89dc868ba37d9cc21b2cece",
+            ),
+            (
+                &[
+                    "shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed",
+                    "shadow pistol academic acid actress prayer class unknown daughter sweater depict flip twice unkind craft early superior advocate guest smoking",
+                ],
+                "b43ceb7e57a0ea8766221624d01b0864",
+            ),
+            (
+                &[
+                    "eraser senior beard romp adorn nuclear spill corner cradle style ancient family general leader ambition exchange unusual garlic promise voice",
+                    "eraser senior ceramic snake clay various huge numb argue hesitate auction category timber browser greatest hanger petition script leaf pickup",
+                    "eraser senior ceramic shaft dynamic become junior wrist silver peasant force math alto coal amazing segment yelp velvet image paces",
+                    "eraser senior ceramic round column hawk trust auction smug shame alive greatest sheriff living perfect corner chest sled fumes adequate",
+                ],
+                "7c3397a292a5941682d7a4ae2d898d11",
+            ),
+            (
+                &["theory painting academic academic armed sweater year military elder discuss acne wildlife boring employer fused large satoshi bundle carbon diagnose anatomy hamster leaves tracks paces beyond phantom capital marvel lips brave detect luck"],
+                "989baf9dcaad5b10ca33dfd8cc75e42477025dce88ae83e75a230086a0e00e92",
+            ),
+        ];
+        for (mnemonics, secret) in vectors {
+            assert_eq!(hex(&combine_mnemonics(mnemonics, "TREZOR").unwrap()), secret);
+            for mnemonic in mnemonics {
+                assert_eq!(Slip39Share::from_mnemonic(mnemonic).unwrap().to_mnemonic().unwrap(), *mnemonic);
+            }
+        }
+        // Test vector 17 needs all three shares of its second group.
+        let (mnemonics, _) = vectors[2];
+        assert_eq!(combine_mnemonics(&mnemonics[..3], "TREZOR"), Err(VsssError::InsufficientShares { required: 2, provided: 1 }));
+    }
+
+    #[test]
+    fn test_generate_and_combine() {
+        let mut rng = ChaCha20Rng::seed_from_u64(39);
+        let secret = b"sixteen byte key, doubled here!!";
+        for extendable in [false, true] {
+            let groups = generate_mnemonics_with_rng(2, &[(1, 1), (2, 3), (3, 5)], secret, "passphrase", extendable, 0, &mut rng).unwrap();
+            assert_eq!(groups.iter().map(Vec::len).collect::<Vec<_>>(), vec![1, 3, 5]);
+            assert!(groups.iter().flatten().all(|m| m.split(' ').count() == 33));
+
+            let mnemonics = [&groups[1][2], &groups[0][0], &groups[1][0]];
+            assert_eq!(combine_mnemonics(&mnemonics, "passphrase").unwrap().as_slice(), secret);
+            assert_ne!(combine_mnemonics(&mnemonics, "other").unwrap().as_slice(), secret);
+            let mnemonics = [&groups[2][4], &groups[1][1], &groups[2][0], &groups[1][2], &groups[2][2]];
+            assert_eq!(combine_mnemonics(&mnemonics, "passphrase").unwrap().as_slice(), secret);
+        }
+    }
+
+    #[test]
+    fn test_rejects_invalid_mnemonics() {
+        let groups = generate_mnemonics(1, &[(2, 3)], &[7; 16], "", true, 0).unwrap();
+        let mnemonic = &groups[0][0];
+
+        let mut words: Vec<&str> = mnemonic.split(' ').collect();
+        words[5] = if words[5] == "acid" { "acne" } else { "acid" };
+        assert_eq!(Slip39Share::from_mnemonic(&words.join(" ")), Err(VsssError::CorruptedText { position: None }));
+        words[5] = "bitcoin";
+        assert_eq!(Slip39Share::from_mnemonic(&words.join(" ")), Err(VsssError::CorruptedText { position: Some(6) }));
+        let share = Slip39Share::from_mnemonic(&mnemonic.to_uppercase()).unwrap();
+        assert_eq!(share.to_mnemonic().unwrap(), *mnemonic);
+        assert!(format!("{:?}", share).contains("value: \"<redacted>\""));
+
+        let other = generate_mnemonics(1, &[(2, 3)], &[7; 16], "", true, 0).unwrap();
+        assert_eq!(combine_mnemonics(&[mnemonic, &other[0][1]], ""), Err(VsssError::MixedDealings));
+        assert_eq!(
+            generate_mnemonics(1, &[(1, 2)], &[7; 16], "", true, 0),
+            Err(VsssError::InvalidThreshold { threshold: 1, num_shares: 2 })
+        );
+        assert_eq!(generate_mnemonics(1, &[(1, 1)], &[7; 15], "", true, 0), Err(VsssError::InvalidEncoding));
+    }
+}
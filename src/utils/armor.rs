@@ -0,0 +1,338 @@
+//! Text encodings of shares for printing on paper and typing back, enabled with the `armor`
+//! feature.
+//!
+//! Both encodings wrap the binary wire format of `Share::to_bytes`, so they carry the same
+//! metadata and are parsed as strictly:
+//!
+//! * `Share::to_bech32` produces a single Bech32m string, such as `feldman-t3-1qf...`, whose
+//!   human-readable prefix names the scheme and the threshold. Bech32m only guarantees to detect
+//!   up to four mistyped characters in strings of at most 89 characters, which the shares of any
+//!   cryptographic group exceed. In a longer string, mistyped characters go unnoticed with a
+//!   probability of about 2^-30, and a single mistyped character is usually located.
+//! * `Share::to_armor` produces a PEM-style block with `Scheme`, `Threshold` and `Index` headers
+//!   and a body of short Base58 lines. Base58 leaves out the look-alike characters `0`, `O`, `I`
+//!   and `l`, and every line carries its own checksum, so a typo is reported with its line.
+//!
+//! ```text
+//! -----BEGIN VSSS SHARE-----
+//! Scheme: Feldman
+//! Threshold: 3
+//! Index: 2
+//!
+//! 2vXk8c1yPQ7rRn9uGmC3YjHfWz6TtLbEa4sKd5N
+//! ...
+//! -----END VSSS SHARE-----
+//! ```
+//!
+//! A corrupted string or line is reported as `VsssError::CorruptedText` with the position of the
+//! typo when it can be determined.
+
+use super::{hash_data, Scheme, Share};
+use crate::error::{Result, VsssError};
+use bech32::primitives::decode::{CheckedHrpstring, UncheckedHrpstring};
+use bech32::{Bech32m, Hrp};
+use num_bigint::BigUint;
+use zeroize::Zeroizing;
+
+/// The first line of an armored share.
+pub const ARMOR_BEGIN: &str = "-----BEGIN VSSS SHARE-----";
+
+/// The last line of an armored share.
+pub const ARMOR_END: &str = "-----END VSSS SHARE-----";
+
+/// The number of wire format bytes on each line of an armored share.
+const LINE_BYTES: usize = 24;
+
+/// The length of the checksum of each line of an armored share.
+const LINE_CHECKSUM_LEN: usize = 2;
+
+/// The characters of the Bech32 alphabet.
+const BECH32_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+impl Scheme {
+    /// Returns the name of the scheme used in text encodings.
+    fn name(self) -> &'static str {
+        match self {
+            Scheme::Shamir => "Shamir",
+            Scheme::Feldman => "Feldman",
+            Scheme::Pedersen => "Pedersen",
+            Scheme::PedersenBlinding => "PedersenBlinding",
+        }
+    }
+}
+
+/// Returns the human-readable prefix of the Bech32m encoding of a share.
+fn bech32_prefix(share: &Share) -> String {
+    format!("{}-t{}-", share.scheme.name().to_lowercase(), share.threshold)
+}
+
+impl Share {
+    /// Encodes the share as a Bech32m string whose human-readable prefix names the scheme and the
+    /// threshold.
+    ///
+    /// # Arguments
+    ///
+    /// * `modulus` - The modulus the share was generated with.
+    ///
+    /// # Returns
+    ///
+    /// The lowercase Bech32m string, or the errors of `to_bytes`. Shares whose wire encoding is too
+    /// long for Bech32m, which happens for moduli above about 4400 bits, are rejected with
+    /// `VsssError::InvalidEncoding`; use `to_armor` for them.
+    pub fn to_bech32(&self, modulus: &BigUint) -> Result<String> {
+        let bytes = Zeroizing::new(self.to_bytes(modulus)?);
+        let hrp = Hrp::parse(&bech32_prefix(self)).map_err(|_| VsssError::InvalidEncoding)?;
+        bech32::encode::<Bech32m>(hrp, &bytes).map_err(|_| VsssError::InvalidEncoding)
+    }
+
+    /// Decodes a share from a Bech32m string produced by `to_bech32`. Uppercase strings, as used
+    /// in QR codes, are accepted.
+    ///
+    /// # Arguments
+    ///
+    /// * `encoded` - The Bech32m string.
+    /// * `modulus` - The modulus the share was generated with.
+    ///
+    /// # Returns
+    ///
+    /// The share, `VsssError::CorruptedText` if the checksum does not match, with the position of
+    /// a single mistyped character when it can be located, or the errors of `from_bytes`. A prefix
+    /// that does not match the encoded scheme and threshold is rejected with
+    /// `VsssError::InvalidEncoding`.
+    pub fn from_bech32(encoded: &str, modulus: &BigUint) -> Result<Share> {
+        let encoded = encoded.trim();
+        let checked = match CheckedHrpstring::new::<Bech32m>(encoded) {
+            Ok(checked) => checked,
+            Err(_) => return Err(VsssError::CorruptedText { position: locate_typo(encoded) }),
+        };
+        let bytes = Zeroizing::new(checked.byte_iter().collect::<Vec<u8>>());
+        // Rejects non-zero padding bits, the only freedom left once the checksum matches.
+        let canonical = bech32::encode::<Bech32m>(checked.hrp(), &bytes).map_err(|_| VsssError::InvalidEncoding)?;
+        if canonical != encoded.to_lowercase() {
+            return Err(VsssError::InvalidEncoding);
+        }
+        let share = Share::from_bytes(&bytes, modulus)?;
+        if checked.hrp().to_lowercase() != bech32_prefix(&share) {
+            return Err(VsssError::InvalidEncoding);
+        }
+        Ok(share)
+    }
+
+    /// Encodes the share as a PEM-style armored block with one checksummed Base58 line per 24
+    /// bytes of the wire format.
+    ///
+    /// # Arguments
+    ///
+    /// * `modulus` - The modulus the share was generated with.
+    ///
+    /// # Returns
+    ///
+    /// The armored block, ending with a newline, or the errors of `to_bytes`.
+    pub fn to_armor(&self, modulus: &BigUint) -> Result<String> {
+        let bytes = Zeroizing::new(self.to_bytes(modulus)?);
+        let mut armor = format!(
+            "{}\nScheme: {}\nThreshold: {}\nIndex: {}\n\n",
+            ARMOR_BEGIN, self.scheme.name(), self.threshold, self.index
+        );
+        for (number, chunk) in bytes.chunks(LINE_BYTES).enumerate() {
+            let mut line = Zeroizing::new(chunk.to_vec());
+            line.extend_from_slice(&line_checksum(number, chunk));
+            armor.push_str(&bs58::encode(line.as_slice()).into_string());
+            armor.push('\n');
+        }
+        armor.push_str(ARMOR_END);
+        armor.push('\n');
+        Ok(armor)
+    }
+
+    /// Decodes a share from an armored block produced by `to_armor`. Blank lines around the block
+    /// and trailing whitespace on its lines are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `armored` - The armored block.
+    /// * `modulus` - The modulus the share was generated with.
+    ///
+    /// # Returns
+    ///
+    /// The share, `VsssError::CorruptedText` with the line number of the first body line that is
+    /// not valid Base58 or fails its checksum, or the errors of `from_bytes`. Missing or
+    /// misplaced delimiters and headers, and headers that disagree with the encoded share, are
+    /// rejected with `VsssError::InvalidEncoding`.
+    pub fn from_armor(armored: &str, modulus: &BigUint) -> Result<Share> {
+        let mut lines = armored
+            .lines()
+            .enumerate()
+            .map(|(number, line)| (number + 1, line.trim_end()))
+            .skip_while(|(_, line)| line.is_empty());
+        let mut next = || lines.next().ok_or(VsssError::InvalidEncoding);
+
+        if next()?.1 != ARMOR_BEGIN {
+            return Err(VsssError::InvalidEncoding);
+        }
+        let scheme = header(next()?.1, "Scheme")?.to_string();
+        let threshold = header(next()?.1, "Threshold")?.to_string();
+        let index = header(next()?.1, "Index")?.to_string();
+        if !next()?.1.is_empty() {
+            return Err(VsssError::InvalidEncoding);
+        }
+
+        let mut bytes = Zeroizing::new(Vec::new());
+        loop {
+            let (number, line) = next()?;
+            if line == ARMOR_END {
+                break;
+            }
+            if line.is_empty() {
+                return Err(VsssError::InvalidEncoding);
+            }
+            let body_line = bytes.len() / LINE_BYTES;
+            let decoded = Zeroizing::new(bs58::decode(line).into_vec().unwrap_or_default());
+            let corrupted = VsssError::CorruptedText { position: Some(number) };
+            if decoded.len() <= LINE_CHECKSUM_LEN || bytes.len() % LINE_BYTES != 0 {
+                return Err(corrupted);
+            }
+            let (chunk, checksum) = decoded.split_at(decoded.len() - LINE_CHECKSUM_LEN);
+            if chunk.len() > LINE_BYTES || line_checksum(body_line, chunk) != checksum {
+                return Err(corrupted);
+            }
+            bytes.extend_from_slice(chunk);
+        }
+        if next().is_ok_and(|(_, line)| !line.is_empty()) || lines.any(|(_, line)| !line.is_empty()) {
+            return Err(VsssError::InvalidEncoding);
+        }
+
+        let share = Share::from_bytes(&bytes, modulus)?;
+        if scheme != share.scheme.name() || threshold != share.threshold.to_string() || index != share.index.to_string() {
+            return Err(VsssError::InvalidEncoding);
+        }
+        Ok(share)
+    }
+}
+
+/// Returns the value of the header line `line` named `name`.
+fn header<'a>(line: &'a str, name: &str) -> Result<&'a str> {
+    line.strip_prefix(name).and_then(|rest| rest.strip_prefix(": ")).ok_or(VsssError::InvalidEncoding)
+}
+
+/// Computes the checksum of the `number`-th line, counted from zero, of an armored share. The line
+/// number is included so that swapped lines are detected as well.
+fn line_checksum(number: usize, chunk: &[u8]) -> [u8; LINE_CHECKSUM_LEN] {
+    let mut data = Zeroizing::new((number as u32).to_be_bytes().to_vec());
+    data.extend_from_slice(chunk);
+    let mut checksum = [0u8; LINE_CHECKSUM_LEN];
+    checksum.copy_from_slice(&hash_data(&data)[..LINE_CHECKSUM_LEN]);
+    checksum
+}
+
+/// Locates a single mistyped character in the data part of a Bech32m string.
+///
+/// Every substitution of a data character by another Bech32 character is tried; the position is
+/// returned if exactly one position can be corrected into a string with a valid checksum, or, failing
+/// that, if exactly one character is not in the Bech32 alphabet at all.
+///
+/// # Returns
+///
+/// The 1-based position of the typo in `encoded`, or `None` if it cannot be determined.
+fn locate_typo(encoded: &str) -> Option<usize> {
+    if !encoded.is_ascii() {
+        return encoded.chars().position(|c| !c.is_ascii()).map(|position| position + 1);
+    }
+    let lowercase = encoded.to_ascii_lowercase();
+    let separator = lowercase.rfind('1')?;
+    let mut candidate = lowercase.clone().into_bytes();
+
+    let mut corrections = Vec::new();
+    let mut invalid = Vec::new();
+    for position in separator + 1..candidate.len() {
+        let original = candidate[position];
+        if !BECH32_CHARSET.contains(&original) {
+            invalid.push(position);
+        }
+        for &replacement in BECH32_CHARSET.iter().filter(|&&c| c != original) {
+            candidate[position] = replacement;
+            let valid = std::str::from_utf8(&candidate)
+                .ok()
+                .and_then(|s| UncheckedHrpstring::new(s).ok())
+                .is_some_and(|s| s.has_valid_checksum::<Bech32m>());
+            if valid {
+                corrections.push(position);
+                break;
+            }
+        }
+        candidate[position] = original;
+    }
+
+    match (corrections.as_slice(), invalid.as_slice()) {
+        ([position], _) | ([], [position]) => Some(position + 1),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feldman_verifiability::FeldmanVSSParams;
+    use crate::secret::Secret;
+    use crate::utils::StandardGroup;
+
+    fn feldman_share() -> (Share, BigUint) {
+        let params = FeldmanVSSParams::from_standard_group(StandardGroup::Ffdhe2048);
+        let (shares, _) = params.generate_shares(&Secret::new(BigUint::from(42u32)), 3, 5).unwrap();
+        (shares[1].clone(), params.q)
+    }
+
+    #[test]
+    fn test_bech32_round_trip() {
+        let (share, modulus) = feldman_share();
+        let encoded = share.to_bech32(&modulus).unwrap();
+        assert!(encoded.starts_with("feldman-t3-1"));
+        assert_eq!(Share::from_bech32(&encoded, &modulus).unwrap(), share);
+        assert_eq!(Share::from_bech32(&encoded.to_uppercase(), &modulus).unwrap(), share);
+
+        let relabeled = bech32::encode::<Bech32m>(Hrp::parse("shamir-t3-").unwrap(), &share.to_bytes(&modulus).unwrap()).unwrap();
+        assert_eq!(Share::from_bech32(&relabeled, &modulus), Err(VsssError::InvalidEncoding));
+    }
+
+    #[test]
+    fn test_bech32_locates_typo() {
+        let modulus = BigUint::from(7919u32);
+        let share = Share::new(BigUint::from(2u32), Secret::new(BigUint::from(1234u32)), 2, crate::utils::group_id(&modulus), [5; 16], Scheme::Shamir);
+        let encoded = share.to_bech32(&modulus).unwrap();
+
+        let mut typo = encoded.clone().into_bytes();
+        let position = encoded.len() - 20;
+        typo[position] = if typo[position] == b'q' { b'p' } else { b'q' };
+        let typo = String::from_utf8(typo).unwrap();
+        assert_eq!(Share::from_bech32(&typo, &modulus), Err(VsssError::CorruptedText { position: Some(position + 1) }));
+
+        let mut invalid = encoded.clone();
+        invalid.replace_range(position..position + 1, "b");
+        assert_eq!(Share::from_bech32(&invalid, &modulus), Err(VsssError::CorruptedText { position: Some(position + 1) }));
+    }
+
+    #[test]
+    fn test_armor_round_trip() {
+        let (share, modulus) = feldman_share();
+        let armored = share.to_armor(&modulus).unwrap();
+        assert!(armored.starts_with("-----BEGIN VSSS SHARE-----\nScheme: Feldman\nThreshold: 3\nIndex: 2\n\n"));
+        assert_eq!(Share::from_armor(&armored, &modulus).unwrap(), share);
+        assert_eq!(Share::from_armor(&format!("\n{}\n", armored.replace('\n', "\r\n")), &modulus).unwrap(), share);
+
+        // A typo on the second body line, the seventh line of the block.
+        let mut lines: Vec<String> = armored.lines().map(str::to_string).collect();
+        let replacement = if lines[6].ends_with('z') { "y" } else { "z" };
+        let last = lines[6].len() - 1;
+        lines[6].replace_range(last.., replacement);
+        assert_eq!(Share::from_armor(&lines.join("\n"), &modulus), Err(VsssError::CorruptedText { position: Some(7) }));
+
+        let mut swapped: Vec<&str> = armored.lines().collect();
+        swapped.swap(5, 6);
+        assert_eq!(Share::from_armor(&swapped.join("\n"), &modulus), Err(VsssError::CorruptedText { position: Some(6) }));
+
+        let relabeled = armored.replace("Index: 2", "Index: 3");
+        assert_eq!(Share::from_armor(&relabeled, &modulus), Err(VsssError::InvalidEncoding));
+        let truncated = armored.replace(ARMOR_END, "");
+        assert_eq!(Share::from_armor(&truncated, &modulus), Err(VsssError::InvalidEncoding));
+    }
+}
@@ -0,0 +1,315 @@
+//! Generation and auditing of Schnorr group parameters `(p, q, g)`.
+//!
+//! Two kinds of groups are supported:
+//! - Safe-prime groups, where `p = 2q + 1` and `g` generates the subgroup of quadratic residues.
+//! - DSA-style groups, where `p = kq + 1` for a much smaller `q`, generated as in FIPS 186-4,
+//!   Appendix A.1.1.2.
+//!
+//! In both cases `p` and `q` are derived deterministically from a random seed, and `g` is derived
+//! from the same seed following FIPS 186-4, Appendix A.2.3. The seed and counter are returned with
+//! the parameters so that any other party can re-derive them with `verify_group_params` and
+//! convince itself that they were not chosen maliciously.
+
+use super::{derive_generator, hash_data, is_probable_prime, mod_exp};
+use crate::error::{Result, VsssError};
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use rand::{thread_rng, CryptoRng, RngCore};
+
+/// Length in bits of the SHA-256 output used to expand the seed.
+const OUTLEN: usize = 256;
+
+/// The smallest supported bit size of a safe-prime modulus.
+const MIN_SAFE_PRIME_BITS: usize = 16;
+
+/// The index used to derive the generator `g` from the domain parameter seed.
+const GENERATOR_INDEX: u8 = 1;
+
+/// Describes how the primes of a `SchnorrGroup` were generated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GroupKind {
+    /// `p = 2q + 1` with `q` prime.
+    SafePrime,
+    /// `p = kq + 1` with `q` of a separately chosen bit length (FIPS 186-4, A.1.1.2).
+    Dsa,
+}
+
+/// Represents a prime-order subgroup of `Z_p^*` together with the data needed to audit it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SchnorrGroup {
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::biguint"))]
+    pub p: BigUint, // Modulus of the group Z_p^*
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::biguint"))]
+    pub q: BigUint, // Prime order of the subgroup, q | p - 1
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::biguint"))]
+    pub g: BigUint, // Generator of the subgroup of order q
+    pub kind: GroupKind, // How p and q were derived
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::bytes"))]
+    pub seed: Vec<u8>, // Domain parameter seed p, q and g were derived from
+    pub counter: usize, // Number of the candidate that produced p
+}
+
+/// Generates a safe-prime group with a `bit_size`-bit modulus `p = 2q + 1`.
+///
+/// # Arguments
+///
+/// * `bit_size` - The bit size of `p`; must be at least 16.
+///
+/// # Returns
+///
+/// A `SchnorrGroup` whose generator has order `q = (p - 1) / 2`, or `VsssError::InvalidGroupParams`
+/// if `bit_size` is too small.
+pub fn generate_safe_prime_group(bit_size: usize) -> Result<SchnorrGroup> {
+    generate_safe_prime_group_with_rng(bit_size, &mut thread_rng())
+}
+
+/// Generates a safe-prime group as `generate_safe_prime_group` does, drawing the seed from `rng`.
+pub fn generate_safe_prime_group_with_rng<R: RngCore + CryptoRng>(bit_size: usize, rng: &mut R) -> Result<SchnorrGroup> {
+    if bit_size < MIN_SAFE_PRIME_BITS {
+        return Err(VsssError::InvalidGroupParams);
+    }
+    let seed = random_seed(OUTLEN / 8, rng);
+    let (p, q, counter) = search_safe_prime(&seed, bit_size, None).expect("unbounded search always succeeds");
+    let g = derive_generator(&p, &q, &seed, GENERATOR_INDEX)?;
+    Ok(SchnorrGroup { p, q, g, kind: GroupKind::SafePrime, seed, counter })
+}
+
+/// Generates a DSA-style group with an `l`-bit modulus `p` and an `n`-bit subgroup order `q`,
+/// following FIPS 186-4, Appendix A.1.1.2 with SHA-256.
+///
+/// # Arguments
+///
+/// * `l` - The bit size of `p`.
+/// * `n` - The bit size of `q`; must satisfy `16 <= n <= 256` and `n < l`.
+///
+/// # Returns
+///
+/// A `SchnorrGroup` whose generator has order `q`, or `VsssError::InvalidGroupParams` if the
+/// `(L, N)` bit sizes are not supported.
+pub fn generate_dsa_group(l: usize, n: usize) -> Result<SchnorrGroup> {
+    generate_dsa_group_with_rng(l, n, &mut thread_rng())
+}
+
+/// Generates a DSA-style group as `generate_dsa_group` does, drawing the seeds from `rng`.
+pub fn generate_dsa_group_with_rng<R: RngCore + CryptoRng>(l: usize, n: usize, rng: &mut R) -> Result<SchnorrGroup> {
+    if !(16..=OUTLEN).contains(&n) || n >= l {
+        return Err(VsssError::InvalidGroupParams);
+    }
+    loop {
+        let seed = random_seed(n.div_ceil(8), rng);
+        if let Some((p, q, counter)) = dsa_primes_from_seed(&seed, l, n) {
+            let g = derive_generator(&p, &q, &seed, GENERATOR_INDEX)?;
+            return Ok(SchnorrGroup { p, q, g, kind: GroupKind::Dsa, seed, counter });
+        }
+    }
+}
+
+/// Re-derives `p`, `q` and `g` from the seed and counter of a `SchnorrGroup` and checks that they
+/// match the claimed values.
+///
+/// # Returns
+///
+/// `true` if the parameters were honestly derived from their seed, otherwise `false`.
+pub fn verify_group_params(group: &SchnorrGroup) -> bool {
+    let l = group.p.bits() as usize;
+    let derived = match group.kind {
+        GroupKind::SafePrime => {
+            if l < MIN_SAFE_PRIME_BITS {
+                return false;
+            }
+            search_safe_prime(&group.seed, l, Some(group.counter))
+        }
+        GroupKind::Dsa => {
+            let n = group.q.bits() as usize;
+            if !(16..=OUTLEN).contains(&n) || n >= l || group.seed.len() * 8 < n {
+                return false;
+            }
+            dsa_primes_from_seed(&group.seed, l, n)
+        }
+    };
+
+    match derived {
+        Some((p, q, counter)) => {
+            p == group.p && q == group.q && counter == group.counter
+                && derive_generator(&p, &q, &group.seed, GENERATOR_INDEX).is_ok_and(|g| g == group.g)
+        }
+        None => false,
+    }
+}
+
+/// Checks that `p` and `q` are prime, that `q` divides `p - 1`, and that every one of `generators`
+/// has order `q` modulo `p`.
+pub(crate) fn is_prime_order_subgroup(p: &BigUint, q: &BigUint, generators: &[&BigUint]) -> bool {
+    let one = BigUint::one();
+    if *q < BigUint::from(2u32) || p <= q {
+        return false;
+    }
+    if !((p - &one) % q).is_zero() {
+        return false;
+    }
+    // As q is prime, x^q = 1 together with x != 1 means x has order exactly q.
+    let generates = |x: &BigUint| one < *x && x < p && mod_exp(x, q, p) == one;
+    generators.iter().all(|x| generates(x)) && is_probable_prime(q) && is_probable_prime(p)
+}
+
+/// Draws a random seed of `len` bytes from `rng`.
+fn random_seed<R: RngCore + CryptoRng>(len: usize, rng: &mut R) -> Vec<u8> {
+    let mut seed = vec![0u8; len];
+    rng.fill_bytes(&mut seed);
+    seed
+}
+
+/// Searches the candidates derived from `seed` for a safe prime `p = 2q + 1` of `bit_size` bits.
+///
+/// When `only` is given, just that candidate is tested. Returns `(p, q, counter)`.
+fn search_safe_prime(seed: &[u8], bit_size: usize, only: Option<usize>) -> Option<(BigUint, BigUint, usize)> {
+    let mut counter = only.unwrap_or(0);
+    loop {
+        let mut input = seed.to_vec();
+        input.extend_from_slice(b"safe");
+        input.extend_from_slice(&(counter as u64).to_be_bytes());
+        let mut q = expand(&input, bit_size - 1);
+        q.set_bit(bit_size as u64 - 2, true);
+        q.set_bit(0, true);
+        let p = (&q << 1) + BigUint::one();
+
+        if !has_small_factor(&q) && !has_small_factor(&p) && is_probable_prime(&q) && is_probable_prime(&p) {
+            return Some((p, q, counter));
+        }
+        if only.is_some() {
+            return None;
+        }
+        counter += 1;
+    }
+}
+
+/// Runs steps 6 to 11 of FIPS 186-4, Appendix A.1.1.2 for a single domain parameter seed.
+///
+/// Returns `(p, q, counter)`, or `None` if the seed does not yield valid primes.
+fn dsa_primes_from_seed(seed: &[u8], l: usize, n: usize) -> Option<(BigUint, BigUint, usize)> {
+    let seedlen = seed.len() * 8;
+    let seed_value = BigUint::from_bytes_be(seed);
+    let seed_modulus = BigUint::one() << seedlen;
+    let count = l.div_ceil(OUTLEN) - 1;
+    let b = l - 1 - count * OUTLEN;
+
+    // Steps 6 to 8: derive q from the seed.
+    let u = BigUint::from_bytes_be(&hash_data(seed)) % (BigUint::one() << (n - 1));
+    let q = (BigUint::one() << (n - 1)) + &u + BigUint::one() - (&u % 2u32);
+    if !is_probable_prime(&q) {
+        return None;
+    }
+
+    // Steps 10 and 11: derive candidates for p.
+    let two_q = &q << 1;
+    let mut offset: usize = 1;
+    for counter in 0..4 * l {
+        let mut w = BigUint::zero();
+        for j in 0..=count {
+            let value = (&seed_value + BigUint::from(offset + j)) % &seed_modulus;
+            let v = BigUint::from_bytes_be(&hash_data(&to_fixed_bytes(&value, seed.len())));
+            let v = if j == count { v % (BigUint::one() << b) } else { v };
+            w += v << (j * OUTLEN);
+        }
+        let x = w + (BigUint::one() << (l - 1));
+        let c: BigUint = &x % &two_q;
+        let p: BigUint = (x + BigUint::one()) - c;
+        if p.bits() as usize == l && !has_small_factor(&p) && is_probable_prime(&p) {
+            return Some((p, q, counter));
+        }
+        offset += count + 1;
+    }
+    None
+}
+
+/// Expands `input` into a `bits`-bit integer by hashing it with a block counter.
+fn expand(input: &[u8], bits: usize) -> BigUint {
+    let mut bytes = Vec::with_capacity(bits.div_ceil(8) + OUTLEN / 8);
+    let mut block: u32 = 0;
+    while bytes.len() * 8 < bits {
+        let mut data = input.to_vec();
+        data.extend_from_slice(&block.to_be_bytes());
+        bytes.extend_from_slice(&hash_data(&data));
+        block += 1;
+    }
+    BigUint::from_bytes_be(&bytes) >> (bytes.len() * 8 - bits)
+}
+
+/// Encodes `value` as exactly `len` big-endian bytes.
+fn to_fixed_bytes(value: &BigUint, len: usize) -> Vec<u8> {
+    let bytes = value.to_bytes_be();
+    let mut out = vec![0u8; len.saturating_sub(bytes.len())];
+    out.extend_from_slice(&bytes[bytes.len().saturating_sub(len)..]);
+    out
+}
+
+/// Cheaply rejects candidates divisible by a small odd prime before running the primality test.
+fn has_small_factor(n: &BigUint) -> bool {
+    const SMALL_PRIMES: [u32; 24] = [
+        3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+    ];
+    SMALL_PRIMES.iter().any(|&prime| {
+        BigUint::from(prime) < *n && (n % prime).is_zero()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::mod_exp;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    #[test]
+    fn test_safe_prime_group() {
+        let group = generate_safe_prime_group(128).unwrap();
+        assert_eq!(group.p.bits(), 128);
+        assert_eq!(group.p, (&group.q << 1) + BigUint::one());
+        assert_eq!(mod_exp(&group.g, &group.q, &group.p), BigUint::one());
+        assert!(verify_group_params(&group));
+    }
+
+    #[test]
+    fn test_dsa_group() {
+        let group = generate_dsa_group(256, 128).unwrap();
+        assert_eq!(group.p.bits(), 256);
+        assert_eq!(group.q.bits(), 128);
+        assert!(((&group.p - BigUint::one()) % &group.q).is_zero());
+        assert_eq!(mod_exp(&group.g, &group.q, &group.p), BigUint::one());
+        assert!(verify_group_params(&group));
+    }
+
+    #[test]
+    fn test_seeded_generation_is_reproducible() {
+        let group = generate_dsa_group_with_rng(256, 128, &mut ChaCha20Rng::seed_from_u64(1)).unwrap();
+        assert_eq!(group, generate_dsa_group_with_rng(256, 128, &mut ChaCha20Rng::seed_from_u64(1)).unwrap());
+        assert!(verify_group_params(&group));
+    }
+
+    #[test]
+    fn test_rejects_tampered_params() {
+        let group = generate_dsa_group(256, 128).unwrap();
+
+        let mut tampered = group.clone();
+        tampered.g = mod_exp(&group.g, &BigUint::from(2u32), &group.p);
+        assert!(!verify_group_params(&tampered));
+
+        let mut tampered = group;
+        tampered.seed[0] ^= 1;
+        assert!(!verify_group_params(&tampered));
+
+        assert_eq!(generate_dsa_group(128, 128), Err(VsssError::InvalidGroupParams));
+        assert_eq!(generate_safe_prime_group(8), Err(VsssError::InvalidGroupParams));
+    }
+
+    #[test]
+    fn test_rejects_tiny_safe_prime_params() {
+        let mut group = generate_safe_prime_group(64).unwrap();
+        for p in [0u32, 1, 2, 7] {
+            group.p = BigUint::from(p);
+            assert!(!verify_group_params(&group));
+        }
+    }
+}
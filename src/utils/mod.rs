@@ -6,17 +6,44 @@ extern crate rand;
 
 use num_bigint::{BigUint, BigInt, RandBigInt, ToBigInt};
 use num_traits::{One,Zero};
-use rand::thread_rng;
+use rand::{thread_rng, CryptoRng, RngCore};
 use num_prime::RandPrime;
 use num_prime::PrimalityTestConfig;
+use num_prime::nt_funcs::is_prime;
 use sha2::{Sha256, Digest};
-
-
-/// Represents a polynomial with coefficients in `BigUint`.
-/// This struct is used for operations such as Shamir's Secret Sharing.
-pub struct Polynomial {
+use std::fmt;
+use crate::algebra::{ModPField, PrimeField};
+use crate::error::{Result, VsssError};
+use crate::secret::zeroize_biguint;
+use zeroize::Zeroize;
+
+pub mod group_params;
+pub use group_params::{generate_dsa_group, generate_dsa_group_with_rng, generate_safe_prime_group, generate_safe_prime_group_with_rng};
+pub use group_params::{verify_group_params, GroupKind, SchnorrGroup};
+pub mod standard_groups;
+pub use standard_groups::StandardGroup;
+#[cfg(feature = "serde")]
+pub mod serialization;
+pub mod share;
+pub use share::{dealing_points, group_id, new_dealing_id, new_dealing_id_with_rng, DealingId, GroupId, Scheme, Share};
+pub mod wire;
+pub mod reed_solomon;
+pub use reed_solomon::berlekamp_welch_over;
+pub mod multi_exp;
+pub use multi_exp::{multi_exp, FixedBaseTable};
+#[cfg(feature = "armor")]
+pub mod armor;
+pub use wire::{commitments_from_bytes, commitments_to_bytes};
+
+/// Represents a polynomial with coefficients in `BigUint` by default, or in the elements of any
+/// `PrimeField`. This struct is used for operations such as Shamir's Secret Sharing.
+///
+/// The coefficients of a sharing polynomial are as sensitive as the secret itself, so formatting
+/// a polynomial redacts them, and `BigUint` polynomials implement `Zeroize` for use with
+/// `zeroize::Zeroizing`.
+pub struct Polynomial<T = BigUint> {
     /// The coefficients of the polynomial.
-    pub coefficients: Vec<BigUint>,
+    pub coefficients: Vec<T>,
 }
 
 impl Polynomial {
@@ -30,9 +57,18 @@ impl Polynomial {
     /// # Returns
     ///
     /// A `Polynomial` instance with randomly generated coefficients.
-
     pub fn new(degree: usize, max_bit_size: usize) -> Self {
-        let mut rng = thread_rng();
+        Polynomial::new_with_rng(degree, max_bit_size, &mut thread_rng())
+    }
+
+    /// Creates a new polynomial with random coefficients drawn from `rng`.
+    ///
+    /// # Arguments
+    ///
+    /// * `degree` - The degree of the polynomial.
+    /// * `max_bit_size` - The maximum bit size for the random coefficients.
+    /// * `rng` - The cryptographically secure random number generator to sample from.
+    pub fn new_with_rng<R: RngCore + CryptoRng>(degree: usize, max_bit_size: usize, rng: &mut R) -> Self {
         let mut coefficients = Vec::with_capacity(degree + 1);
 
         let n = BigUint::one() << max_bit_size;
@@ -53,18 +89,21 @@ impl Polynomial {
     /// # Arguments
     ///
     /// * `threshold`: The threshold number of shares needed to reconstruct the secret. This also
-    /// determines the degree of the polynomial, which will be `threshold - 1`.
+    ///   determines the degree of the polynomial, which will be `threshold - 1`.
     /// * `secret_bits`: The number of bits of the secret. This is used to determine the range of
-    /// random coefficients generated for the polynomial's terms, ensuring they are of a similar
-    /// magnitude to the secret.
+    ///   random coefficients generated for the polynomial's terms, ensuring they are of a similar
+    ///   magnitude to the secret.
     /// * `secret`: A reference to the `BigUint` representing the secret to be shared. This value
-    /// will be used as the constant term of the polynomial.
+    ///   will be used as the constant term of the polynomial.
     ///
     /// # Returns
     ///
     /// A `Polynomial` instance with the specified secret embedded as the constant term, and random
-    /// coefficients for the other terms, making it suitable for use in Shamir's Secret Sharing.
+    /// coefficients for the other terms.
     ///
+    /// The coefficients are not uniform modulo any field, so shares leak the size of the secret.
+    /// Use `new_for_shamir_mod` for secret sharing.
+    #[deprecated(note = "coefficients are not uniform in the field; use `Polynomial::new_for_shamir_mod`")]
     pub fn new_for_shamir(threshold: usize, secret_bits: usize, secret: &BigUint) -> Self {
         let mut rng = thread_rng();
         let mut coefficients = vec![secret.clone()];
@@ -76,6 +115,39 @@ impl Polynomial {
 
         Polynomial { coefficients }
     }
+    /// Creates a polynomial for Shamir's Secret Sharing over the integers modulo `modulus`.
+    ///
+    /// The secret is the constant term, and the `threshold - 1` other coefficients are sampled
+    /// uniformly from `[0, modulus)`, so that any `threshold - 1` evaluations at non-zero points are
+    /// independent of the secret.
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold`: The number of shares needed to reconstruct the secret, one more than the degree.
+    /// * `secret`: The secret, which becomes the constant term.
+    /// * `modulus`: The prime modulus of the field the coefficients live in.
+    ///
+    /// # Returns
+    ///
+    /// The polynomial, or `VsssError::SecretOutOfRange` if the secret is not smaller than the modulus.
+    pub fn new_for_shamir_mod(threshold: usize, secret: &BigUint, modulus: &BigUint) -> Result<Self> {
+        Polynomial::new_for_shamir_mod_with_rng(threshold, secret, modulus, &mut thread_rng())
+    }
+
+    /// Creates a polynomial for Shamir's Secret Sharing as `new_for_shamir_mod` does, sampling the
+    /// coefficients from `rng`.
+    pub fn new_for_shamir_mod_with_rng<R: RngCore + CryptoRng>(
+        threshold: usize,
+        secret: &BigUint,
+        modulus: &BigUint,
+        rng: &mut R,
+    ) -> Result<Self> {
+        if secret >= modulus {
+            return Err(VsssError::SecretOutOfRange);
+        }
+        Ok(Polynomial::random_over_with_rng(&ModPField::new(modulus.clone()), secret, threshold, rng))
+    }
+
     /// Evaluates the polynomial at a given point `x`.
     ///
     /// # Arguments
@@ -96,16 +168,68 @@ impl Polynomial {
 
         result
     }
+}
+
+impl<T: Clone> Polynomial<T> {
+    /// Creates a polynomial of degree `threshold - 1` over `field` with the given constant term and
+    /// the remaining coefficients sampled uniformly from the field.
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - The field the coefficients are drawn from.
+    /// * `constant` - The constant term, e.g. the secret in Shamir's Secret Sharing.
+    /// * `threshold` - The number of coefficients, one more than the degree.
+    pub fn random_over<F: PrimeField<Element = T>>(field: &F, constant: &T, threshold: usize) -> Self {
+        Polynomial::random_over_with_rng(field, constant, threshold, &mut thread_rng())
+    }
+
+    /// Creates a polynomial as `random_over` does, sampling the coefficients from `rng`.
+    pub fn random_over_with_rng<F: PrimeField<Element = T>, R: RngCore + CryptoRng>(
+        field: &F,
+        constant: &T,
+        threshold: usize,
+        rng: &mut R,
+    ) -> Self {
+        let mut coefficients = Vec::with_capacity(threshold.max(1));
+        coefficients.push(constant.clone());
+        coefficients.extend((1..threshold).map(|_| field.random_with_rng(rng)));
+        Polynomial { coefficients }
+    }
+
+    /// Evaluates the polynomial at `x` over `field`, using Horner's rule.
+    pub fn evaluate_over<F: PrimeField<Element = T>>(&self, field: &F, x: &T) -> T {
+        self.coefficients.iter().rev().fold(field.zero(), |acc, coef| {
+            field.add(&field.mul(&acc, x), coef)
+        })
+    }
 
-    /// Returns a string representation of the polynomial.
-    pub fn to_string(&self) -> String {
-        self.coefficients.iter().enumerate().map(|(index, coef)| {
+    /// Overwrites every coefficient with `field.zeroize_element` and empties the polynomial.
+    pub fn zeroize_over<F: PrimeField<Element = T>>(&mut self, field: &F) {
+        self.coefficients.iter_mut().for_each(|coef| field.zeroize_element(coef));
+        self.coefficients.clear();
+    }
+}
+
+impl<T> fmt::Display for Polynomial<T> {
+    /// Formats the shape of the polynomial as `a0 + a1x + a2x^2 + ...`, without the values of
+    /// its coefficients.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let terms = (0..self.coefficients.len()).map(|index| {
             match index {
-                0 => format!("{}", coef),
-                1 => format!("{}x", coef),
-                _ => format!("{}x^{}", coef, index),
+                0 => "a0".to_string(),
+                1 => "a1x".to_string(),
+                _ => format!("a{}x^{}", index, index),
             }
-        }).collect::<Vec<String>>().join(" + ")
+        }).collect::<Vec<String>>().join(" + ");
+        write!(f, "{}", terms)
+    }
+}
+
+impl Zeroize for Polynomial {
+    /// Overwrites every coefficient and empties the polynomial.
+    fn zeroize(&mut self) {
+        self.coefficients.iter_mut().for_each(zeroize_biguint);
+        self.coefficients.clear();
     }
 }
 
@@ -124,9 +248,12 @@ impl Polynomial {
 ///
 /// Returns a `BigUint` representing the randomly generated number within the specified range.
 ///
-
 pub fn gen_rand(modulus: &BigUint) -> BigUint{
-    let mut rng = thread_rng();
+    gen_rand_with_rng(modulus, &mut thread_rng())
+}
+
+/// Generates a random number in `[1, modulus)` as `gen_rand` does, drawing from `rng`.
+pub fn gen_rand_with_rng<R: RngCore + CryptoRng>(modulus: &BigUint, rng: &mut R) -> BigUint {
     rng.gen_biguint_range(&BigUint::one(), modulus)
 }
 
@@ -141,10 +268,28 @@ pub fn gen_rand(modulus: &BigUint) -> BigUint{
 ///
 /// A `BigUint` representing the generated prime number.
 pub fn generate_prime(bit_size: usize) -> BigUint {
-    let mut rng = thread_rng();
+    generate_prime_with_rng(bit_size, &mut thread_rng())
+}
+
+/// Generates a prime number of a specified bit size as `generate_prime` does, drawing the
+/// candidates from `rng`.
+pub fn generate_prime_with_rng<R: RngCore + CryptoRng>(bit_size: usize, rng: &mut R) -> BigUint {
     let config = PrimalityTestConfig::default();
     rng.gen_prime(bit_size, Some(config))
 }
+
+/// Checks whether a number is prime using a probabilistic primality test.
+///
+/// # Arguments
+///
+/// * `n` - The number to test.
+///
+/// # Returns
+///
+/// `true` if `n` is prime with overwhelming probability, `false` if it is certainly composite.
+pub fn is_probable_prime(n: &BigUint) -> bool {
+    is_prime(n, Some(PrimalityTestConfig::default())).probably()
+}
 /// Hashes input data using SHA-256.
 ///
 /// # Arguments
@@ -160,6 +305,63 @@ pub fn hash_data(data: &[u8]) -> Vec<u8> {
     hasher.finalize().to_vec()
 }
 
+/// Derives a generator of the subgroup of order `q` of `Z_p^*` by hashing a seed into the group,
+/// following the verifiable canonical generation of FIPS 186-4, Appendix A.2.3 with SHA-256.
+///
+/// Since the generator is the output of a hash, nobody knows its discrete logarithm with respect
+/// to any other generator, and anyone holding the seed can re-derive it.
+///
+/// # Arguments
+///
+/// * `p` - The group modulus.
+/// * `q` - The prime subgroup order, which must divide `p - 1`.
+/// * `seed` - The domain parameter seed.
+/// * `index` - Distinguishes independent generators derived from the same seed.
+///
+/// # Returns
+///
+/// A `BigUint` generator of the subgroup of order `q`, or `VsssError::InvalidGroupParams` if `q`
+/// does not divide `p - 1` or no generator is found within `u16::MAX` attempts, as happens for
+/// degenerate groups such as `p = 2`.
+pub fn derive_generator(p: &BigUint, q: &BigUint, seed: &[u8], index: u8) -> Result<BigUint> {
+    if *q < BigUint::from(2u32) || p <= q || !((p - BigUint::one()) % q).is_zero() {
+        return Err(VsssError::InvalidGroupParams);
+    }
+    let e = (p - BigUint::one()) / q;
+    for count in 1..=u16::MAX {
+        let mut data = Vec::with_capacity(seed.len() + 7);
+        data.extend_from_slice(seed);
+        data.extend_from_slice(b"ggen");
+        data.push(index);
+        data.extend_from_slice(&count.to_be_bytes());
+        let w = BigUint::from_bytes_be(&hash_data(&data));
+        let g = mod_exp(&w, &e, p);
+        if g >= BigUint::from(2u32) {
+            return Ok(g);
+        }
+    }
+    Err(VsssError::InvalidGroupParams)
+}
+
+/// Maps the share indices `1..=num_shares` into `field`.
+///
+/// # Returns
+///
+/// The indices, or `VsssError::TooManyShares` if one of them maps to zero, which would make its
+/// share the secret itself, or to the same element as an earlier index.
+pub(crate) fn share_indices_over<F: PrimeField>(field: &F, num_shares: usize) -> Result<Vec<F::Element>> {
+    let zero = field.zero();
+    let mut indices: Vec<F::Element> = Vec::with_capacity(num_shares);
+    for i in 1..=num_shares as u64 {
+        let x = field.element(i);
+        if x == zero || indices.contains(&x) {
+            return Err(VsssError::TooManyShares { num_shares, max: indices.len() });
+        }
+        indices.push(x);
+    }
+    Ok(indices)
+}
+
 /// Calculates the modular exponentiation of a base raised to an exponent modulo a modulus.
 ///
 /// # Arguments
@@ -203,15 +405,16 @@ pub fn egcd(a: BigInt, b: BigInt) -> (BigInt, BigInt, BigInt) {
 /// 
 /// # Returns
 /// 
-/// An `Option<BigUint>` representing the modular multiplicative inverse of `a` modulo `m` if it exists.
-pub fn mod_inv(a: &BigUint, m: &BigUint) -> Option<BigUint> {
+/// The modular multiplicative inverse of `a` modulo `m`, or `VsssError::NonInvertible` if `a` and
+/// `m` are not coprime.
+pub fn mod_inv(a: &BigUint, m: &BigUint) -> Result<BigUint> {
     let (g, x, _) = egcd(a.to_bigint().unwrap(), m.to_bigint().unwrap());
     if g == One::one() {
         let x_mod_m = ((x % m.to_bigint().unwrap()) + m.to_bigint().unwrap()) % m.to_bigint().unwrap();
         
-        Some(x_mod_m.to_biguint().unwrap())
+        Ok(x_mod_m.to_biguint().unwrap())
     } else {
-        None
+        Err(VsssError::NonInvertible)
     }
 }
 
@@ -225,41 +428,110 @@ pub fn mod_inv(a: &BigUint, m: &BigUint) -> Option<BigUint> {
 /// # Parameters
 ///
 /// * `points`: A slice of tuples where each tuple contains two `BigUint` values. The first element of each tuple
-/// represents the x-coordinate, and the second element represents the y-coordinate of a point on the polynomial.
+///   represents the x-coordinate, and the second element represents the y-coordinate of a point on the polynomial.
 /// * `modulus`: A reference to a `BigUint` value representing the modulus for the finite field operations.
 ///
 /// # Returns
 ///
-/// Returns `Some(BigUint)` representing the secret (the polynomial evaluated at zero) if the inverse of the
-/// denominator exists for all terms in the interpolation formula. Otherwise, returns `None`.
+/// Returns the secret (the polynomial evaluated at zero) if the inverse of the denominator exists for all
+/// terms in the interpolation formula. Otherwise, returns `VsssError::DuplicateShareIndex` if two points share
+/// an x-coordinate, or `VsssError::NonInvertible` if the modulus is not prime.
 ///
+pub fn lagrange_interpolation_zero(points: &[(BigUint, BigUint)], modulus: &BigUint) -> Result<BigUint> {
+    lagrange_interpolation_zero_over(&ModPField::new(modulus.clone()), points)
+}
 
-pub fn lagrange_interpolation_zero(points: &[(BigUint, BigUint)], modulus: &BigUint) -> Option<BigUint> {
-    let mut secret = BigUint::zero();
+/// Performs Lagrange interpolation at zero over an arbitrary field.
+///
+/// This is the field-generic form of `lagrange_interpolation_zero`, usable with any `PrimeField`
+/// implementation such as elliptic curve scalars.
+///
+/// # Parameters
+///
+/// * `field`: The field the points live in.
+/// * `points`: A slice of `(x, y)` tuples of field elements.
+///
+/// # Returns
+///
+/// Returns the interpolated polynomial evaluated at zero, or an error as for `lagrange_interpolation_over`.
+pub fn lagrange_interpolation_zero_over<F: PrimeField>(
+    field: &F,
+    points: &[(F::Element, F::Element)],
+) -> Result<F::Element> {
+    lagrange_interpolation_over(field, points, &field.zero())
+}
+
+/// Performs Lagrange interpolation at an arbitrary point `x` for a given set of points modulo a given modulus.
+///
+/// This is the general form of `lagrange_interpolation_zero`: it evaluates the unique polynomial of
+/// degree less than `points.len()` passing through `points` at `x`. It is used to check whether a
+/// larger set of evaluations is consistent with a polynomial of a bounded degree.
+///
+/// # Parameters
+///
+/// * `points`: A slice of `(x, y)` tuples describing points on the polynomial.
+/// * `x`: A reference to the `BigUint` at which the interpolated polynomial is evaluated.
+/// * `modulus`: A reference to a `BigUint` value representing the modulus for the finite field operations.
+///
+/// # Returns
+///
+/// Returns the value of the interpolated polynomial at `x`, or an error as for `lagrange_interpolation_over`.
+pub fn lagrange_interpolation(points: &[(BigUint, BigUint)], x: &BigUint, modulus: &BigUint) -> Result<BigUint> {
+    lagrange_interpolation_over(&ModPField::new(modulus.clone()), points, x)
+}
+
+/// Performs Lagrange interpolation at an arbitrary point `x` over an arbitrary field.
+///
+/// # Parameters
+///
+/// * `field`: The field the points live in.
+/// * `points`: A slice of `(x, y)` tuples of field elements.
+/// * `x`: The field element at which the interpolated polynomial is evaluated.
+///
+/// # Returns
+///
+/// Returns the value of the interpolated polynomial at `x`. Fails with `VsssError::InsufficientShares`
+/// if `points` is empty, `VsssError::DuplicateShareIndex` if two points share an x-coordinate, and
+/// `VsssError::NonInvertible` if a denominator has no inverse.
+pub fn lagrange_interpolation_over<F: PrimeField>(
+    field: &F,
+    points: &[(F::Element, F::Element)],
+    x: &F::Element,
+) -> Result<F::Element> {
+    if points.is_empty() {
+        return Err(VsssError::InsufficientShares { required: 1, provided: 0 });
+    }
+    for (i, (x_i, _)) in points.iter().enumerate() {
+        if points[..i].iter().any(|(x_j, _)| field.sub(x_i, x_j) == field.zero()) {
+            return Err(VsssError::DuplicateShareIndex);
+        }
+    }
+
+    let mut result = field.zero();
 
     for (i, (x_i, y_i)) in points.iter().enumerate() {
-        let mut numerator = BigUint::one();
-        let mut denominator = BigUint::one();
+        let mut numerator = field.one();
+        let mut denominator = field.one();
 
         for (j, (x_j, _)) in points.iter().enumerate() {
             if i != j {
-                let x_diff = (modulus - x_j) % modulus;
-                numerator = (numerator * x_diff) % modulus;
-                denominator = (denominator * (x_i + modulus - x_j) % modulus) % modulus;
+                numerator = field.mul(&numerator, &field.sub(x, x_j));
+                denominator = field.mul(&denominator, &field.sub(x_i, x_j));
             }
         }
-        let inv_denominator = mod_inv(&denominator, modulus)?;
-        let term = (y_i * &numerator * inv_denominator) % modulus;        
-        secret = (secret + term) % modulus;
+        let inv_denominator = field.inv(&denominator).ok_or(VsssError::NonInvertible)?;
+        let term = field.mul(&field.mul(y_i, &numerator), &inv_denominator);
+        result = field.add(&result, &term);
     }
-    Some(secret)
+    Ok(result)
 }
 
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use num_bigint::ToBigUint;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
 
     // Test for converting polynomial to string representation
     #[test]
@@ -268,9 +540,34 @@ mod tests {
             coefficients: vec![1.to_biguint().unwrap(), 2.to_biguint().unwrap(), 3.to_biguint().unwrap()],
         };
 
-        let expected = "1 + 2x + 3x^2".to_string();
+        let expected = "a0 + a1x + a2x^2".to_string();
         assert_eq!(poly.to_string(), expected);
     }
+
+    // Test that zeroizing a polynomial wipes its coefficients
+    #[test]
+    fn test_polynomial_zeroize() {
+        let modulus = 7919.to_biguint().unwrap();
+        let mut poly = Polynomial::new_for_shamir_mod(3, &42.to_biguint().unwrap(), &modulus).unwrap();
+        poly.zeroize();
+        assert!(poly.coefficients.is_empty());
+
+        let field = crate::algebra::ModPField::new(modulus);
+        let mut poly = Polynomial::random_over(&field, &42.to_biguint().unwrap(), 3);
+        poly.zeroize_over(&field);
+        assert!(poly.coefficients.is_empty());
+    }
+    #[test]
+    fn test_new_for_shamir_mod() {
+        let modulus = 7919.to_biguint().unwrap();
+        let secret = 42.to_biguint().unwrap();
+        let poly = Polynomial::new_for_shamir_mod(3, &secret, &modulus).unwrap();
+        assert_eq!(poly.coefficients.len(), 3);
+        assert_eq!(poly.coefficients[0], secret);
+        assert!(poly.coefficients.iter().all(|coef| coef < &modulus));
+        assert!(matches!(Polynomial::new_for_shamir_mod(3, &modulus, &modulus), Err(VsssError::SecretOutOfRange)));
+    }
+
     #[test]
     fn test_evaluation(){
         let poly = Polynomial {
@@ -288,6 +585,20 @@ mod tests {
         println!("Prime:{}", prime);
     }
 
+    // Test that a seeded RNG makes sampling reproducible
+    #[test]
+    fn test_seeded_rng() {
+        let modulus = 7919.to_biguint().unwrap();
+        let secret = 42.to_biguint().unwrap();
+        let first = Polynomial::new_for_shamir_mod_with_rng(4, &secret, &modulus, &mut ChaCha20Rng::seed_from_u64(7)).unwrap();
+        let second = Polynomial::new_for_shamir_mod_with_rng(4, &secret, &modulus, &mut ChaCha20Rng::seed_from_u64(7)).unwrap();
+        assert_eq!(first.coefficients, second.coefficients);
+
+        let prime = generate_prime_with_rng(64, &mut ChaCha20Rng::seed_from_u64(7));
+        assert_eq!(prime, generate_prime_with_rng(64, &mut ChaCha20Rng::seed_from_u64(7)));
+        assert!(is_probable_prime(&prime));
+    }
+
     // Test for hashing data
     #[test]
     fn test_hash_data() {
@@ -326,5 +637,24 @@ mod tests {
         let modulus = 1009.to_biguint().unwrap();
         let secret = lagrange_interpolation_zero(&points, &modulus).unwrap();
         assert_eq!(secret, 687.to_biguint().unwrap());
+
+        let duplicated = vec![points[0].clone(), points[0].clone()];
+        assert_eq!(lagrange_interpolation_zero(&duplicated, &modulus), Err(VsssError::DuplicateShareIndex));
+        assert_eq!(lagrange_interpolation_zero(&points, &1000.to_biguint().unwrap()), Err(VsssError::NonInvertible));
+    }
+
+    // Test for Lagrange interpolation at an arbitrary point
+    #[test]
+    fn test_lagrange_interpolation() {
+        let points = vec![
+            (1.to_biguint().unwrap(), 90.to_biguint().unwrap()),
+            (2.to_biguint().unwrap(), 87.to_biguint().unwrap()),
+            (3.to_biguint().unwrap(), 678.to_biguint().unwrap())
+        ];
+        let modulus = 1009.to_biguint().unwrap();
+        let at_zero = lagrange_interpolation(&points, &BigUint::zero(), &modulus).unwrap();
+        assert_eq!(at_zero, 687.to_biguint().unwrap());
+        let at_two = lagrange_interpolation(&points, &2.to_biguint().unwrap(), &modulus).unwrap();
+        assert_eq!(at_two, 87.to_biguint().unwrap());
     }
 }
@@ -0,0 +1,224 @@
+//! Faster modular exponentiations for commitments.
+//!
+//! Feldman commitments are powers of one fixed generator `g`, and verifying a share takes a
+//! product of powers of the commitments. Both are sped up here:
+//!
+//! - `FixedBaseTable` precomputes a Lim–Lee comb for a base, after which every power of it costs a
+//!   fraction of the squarings and multiplications of `mod_exp`.
+//! - `multi_exp` computes a product `b_1^e_1 * ... * b_n^e_n` sharing the squarings between all
+//!   the bases: Straus' interleaved windows for a few bases, and Pippenger's buckets for many.
+
+use super::mod_exp;
+use num_bigint::BigUint;
+use num_traits::One;
+
+/// The number of teeth of the comb of a `FixedBaseTable`, which then holds `2^COMB_TEETH` powers.
+pub const COMB_TEETH: usize = 8;
+
+/// The window width of the Straus multi-exponentiation.
+const STRAUS_WINDOW: usize = 4;
+
+/// The number of bases from which `multi_exp` switches from Straus' to Pippenger's method.
+const PIPPENGER_THRESHOLD: usize = 32;
+
+/// A Lim–Lee comb of precomputed powers of a fixed base, for exponents of up to a given size.
+///
+/// An exponent of `b` bits is cut into `COMB_TEETH` rows of `d = b / COMB_TEETH` bits, and the
+/// table holds the products of `base^(2^(k * d))` for every subset of rows `k`. A power then costs
+/// `d` squarings and `d` multiplications instead of about `b` squarings.
+#[derive(Clone)]
+pub struct FixedBaseTable {
+    pub base: BigUint, // Base the table raises to powers
+    pub modulus: BigUint, // Modulus of the powers
+    spacing: usize, // Distance d between the teeth of the comb, in bits
+    powers: Vec<BigUint>, // Entry s is the product of base^(2^(k * d)) over the bits k set in s
+}
+
+impl FixedBaseTable {
+    /// Precomputes the comb of `base` modulo `modulus` for exponents of at most `exponent_bits`
+    /// bits.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The fixed base, e.g. the generator `g` of a group.
+    /// * `modulus` - The modulus of the powers.
+    /// * `exponent_bits` - The bit length of the largest exponent, e.g. that of the group order.
+    pub fn new(base: &BigUint, modulus: &BigUint, exponent_bits: usize) -> Self {
+        let spacing = exponent_bits.div_ceil(COMB_TEETH).max(1);
+        let mut teeth = Vec::with_capacity(COMB_TEETH);
+        teeth.push(base % modulus);
+        for k in 1..COMB_TEETH {
+            let mut tooth = teeth[k - 1].clone();
+            for _ in 0..spacing {
+                tooth = &tooth * &tooth % modulus;
+            }
+            teeth.push(tooth);
+        }
+
+        let mut powers = vec![BigUint::one() % modulus];
+        for tooth in &teeth {
+            let higher: Vec<BigUint> = powers.iter().map(|power| power * tooth % modulus).collect();
+            powers.extend(higher);
+        }
+        FixedBaseTable { base: base.clone(), modulus: modulus.clone(), spacing, powers }
+    }
+
+    /// Raises the base to `exponent` modulo the modulus.
+    ///
+    /// # Returns
+    ///
+    /// `base^exponent mod modulus`, computed with `mod_exp` if the exponent is longer than the
+    /// table was built for.
+    pub fn pow(&self, exponent: &BigUint) -> BigUint {
+        if exponent.bits() > (self.spacing * COMB_TEETH) as u64 {
+            return mod_exp(&self.base, exponent, &self.modulus);
+        }
+        let digits = exponent.to_u64_digits();
+        let mut result = BigUint::one() % &self.modulus;
+        for i in (0..self.spacing).rev() {
+            result = &result * &result % &self.modulus;
+            let index = (0..COMB_TEETH).fold(0, |index, k| index | (bit(&digits, k * self.spacing + i) << k));
+            if index != 0 {
+                result = result * &self.powers[index] % &self.modulus;
+            }
+        }
+        result
+    }
+}
+
+/// Computes the product of `bases[k]^exponents[k]` modulo `modulus`.
+///
+/// # Arguments
+///
+/// * `bases` - The bases, as many as the exponents.
+/// * `exponents` - The exponents, of any size.
+/// * `modulus` - The modulus of the product.
+///
+/// # Returns
+///
+/// The product, or `1` if there are no bases.
+///
+/// # Panics
+///
+/// If `bases` and `exponents` have different lengths.
+pub fn multi_exp(bases: &[BigUint], exponents: &[BigUint], modulus: &BigUint) -> BigUint {
+    assert_eq!(bases.len(), exponents.len(), "multi_exp needs one exponent per base");
+    let bits = exponents.iter().map(|exponent| exponent.bits() as usize).max().unwrap_or(0);
+    let digits: Vec<Vec<u64>> = exponents.iter().map(BigUint::to_u64_digits).collect();
+    if bases.len() < PIPPENGER_THRESHOLD {
+        straus(bases, &digits, bits, modulus)
+    } else {
+        pippenger(bases, &digits, bits, modulus)
+    }
+}
+
+/// Straus' method: one table of `2^STRAUS_WINDOW` powers per base, and the squarings shared
+/// between all the bases.
+fn straus(bases: &[BigUint], digits: &[Vec<u64>], bits: usize, modulus: &BigUint) -> BigUint {
+    let tables: Vec<Vec<BigUint>> = bases.iter().map(|base| {
+        let base = base % modulus;
+        let mut table = vec![BigUint::one() % modulus];
+        for d in 1..1 << STRAUS_WINDOW {
+            table.push(&table[d - 1] * &base % modulus);
+        }
+        table
+    }).collect();
+
+    let mut result = BigUint::one() % modulus;
+    for window in (0..bits.div_ceil(STRAUS_WINDOW)).rev() {
+        for _ in 0..STRAUS_WINDOW {
+            result = &result * &result % modulus;
+        }
+        for (table, digits) in tables.iter().zip(digits) {
+            let digit = window_digit(digits, window * STRAUS_WINDOW, STRAUS_WINDOW);
+            if digit != 0 {
+                result = result * &table[digit] % modulus;
+            }
+        }
+    }
+    result
+}
+
+/// Pippenger's bucket method: in each window, the bases are multiplied into the bucket of their
+/// digit, and the buckets are combined with a running product.
+fn pippenger(bases: &[BigUint], digits: &[Vec<u64>], bits: usize, modulus: &BigUint) -> BigUint {
+    let width = (usize::BITS - bases.len().leading_zeros()).saturating_sub(2).max(2) as usize;
+    let mut result = BigUint::one() % modulus;
+    for window in (0..bits.div_ceil(width)).rev() {
+        for _ in 0..width {
+            result = &result * &result % modulus;
+        }
+        let mut buckets: Vec<Option<BigUint>> = vec![None; 1 << width];
+        for (base, digits) in bases.iter().zip(digits) {
+            let digit = window_digit(digits, window * width, width);
+            if digit != 0 {
+                buckets[digit] = Some(match buckets[digit].take() {
+                    Some(bucket) => bucket * base % modulus,
+                    None => base % modulus,
+                });
+            }
+        }
+
+        // The product of bucket[d]^d, as the product of the running products from the top down.
+        let mut running = BigUint::one();
+        let mut sum = BigUint::one();
+        for bucket in buckets[1..].iter().rev() {
+            if let Some(bucket) = bucket {
+                running = running * bucket % modulus;
+            }
+            if !running.is_one() {
+                sum = sum * &running % modulus;
+            }
+        }
+        result = result * sum % modulus;
+    }
+    result
+}
+
+/// Returns bit `index` of the number whose little-endian 64-bit digits are `digits`.
+fn bit(digits: &[u64], index: usize) -> usize {
+    digits.get(index / 64).map_or(0, |digit| ((digit >> (index % 64)) & 1) as usize)
+}
+
+/// Returns the `width` bits of the number with little-endian 64-bit `digits` starting at bit
+/// `start`.
+fn window_digit(digits: &[u64], start: usize, width: usize) -> usize {
+    (0..width).fold(0, |digit, k| digit | (bit(digits, start + k) << k))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::StandardGroup;
+    use num_bigint::RandBigInt;
+    use num_traits::Zero;
+    use rand::thread_rng;
+
+    #[test]
+    fn test_fixed_base_table() {
+        let group = StandardGroup::Ffdhe2048;
+        let (g, p, q) = (group.g(), group.p(), group.q());
+        let table = FixedBaseTable::new(&g, &p, q.bits() as usize);
+        let mut rng = thread_rng();
+        for exponent in [BigUint::zero(), BigUint::one(), &q - 1u32, rng.gen_biguint_below(&q), &p + 5u32] {
+            assert_eq!(table.pow(&exponent), mod_exp(&g, &exponent, &p));
+        }
+    }
+
+    #[test]
+    fn test_multi_exp() {
+        let p = StandardGroup::Modp2048.p();
+        let mut rng = thread_rng();
+        for count in [0, 1, 5, PIPPENGER_THRESHOLD + 3] {
+            let bases: Vec<BigUint> = (0..count).map(|_| rng.gen_biguint_below(&p)).collect();
+            let mut exponents: Vec<BigUint> = (0..count).map(|_| rng.gen_biguint(300)).collect();
+            if let Some(first) = exponents.first_mut() {
+                *first = BigUint::zero();
+            }
+            let expected = bases.iter().zip(&exponents).fold(BigUint::one(), |acc, (base, exponent)| {
+                acc * mod_exp(base, exponent, &p) % &p
+            });
+            assert_eq!(multi_exp(&bases, &exponents, &p), expected);
+        }
+    }
+}
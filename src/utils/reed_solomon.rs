@@ -0,0 +1,177 @@
+//! Reed–Solomon decoding of Shamir shares.
+//!
+//! The shares of a dealing with threshold `t` are evaluations of a polynomial of degree below `t`,
+//! that is, a Reed–Solomon codeword. Given `n` of them, the Berlekamp–Welch algorithm recovers the
+//! polynomial even if up to `(n - t) / 2` shares were corrupted, and tells which ones they were.
+//!
+//! With `e` errors allowed, it looks for a monic error locator `E` of degree `e` and a polynomial
+//! `Q` of degree below `t + e` such that `Q(x_i) = y_i * E(x_i)` for every share: a linear system
+//! of `n` equations in `t + 2e` unknowns. The roots of `E` cover the corrupted indices, and the
+//! sharing polynomial is the exact quotient `Q / E`.
+
+use super::Polynomial;
+use crate::algebra::PrimeField;
+use crate::error::{Result, VsssError};
+
+/// Decodes points of a polynomial of degree below `threshold` with the Berlekamp–Welch algorithm.
+///
+/// # Arguments
+///
+/// * `field` - The field the points live in.
+/// * `points` - The `(x, y)` points, possibly with some of the `y` values corrupted.
+/// * `threshold` - One more than the degree of the polynomial.
+///
+/// # Returns
+///
+/// The polynomial and the positions in `points` of the points that do not lie on it, at most
+/// `(points.len() - threshold) / 2` of them. Fails with `VsssError::InsufficientShares` if there are
+/// fewer points than the threshold, `VsssError::DuplicateShareIndex` if two points share an
+/// x-coordinate, and `VsssError::InconsistentShares` if too many points are corrupted to decode.
+///
+/// When more than `(points.len() - threshold) / 2` points are corrupted, the points may also happen
+/// to lie close to another polynomial, which is then returned: no decoder can tell the difference.
+pub fn berlekamp_welch_over<F: PrimeField>(
+    field: &F,
+    points: &[(F::Element, F::Element)],
+    threshold: usize,
+) -> Result<(Polynomial<F::Element>, Vec<usize>)> {
+    if threshold == 0 || points.len() < threshold {
+        return Err(VsssError::InsufficientShares { required: threshold.max(1), provided: points.len() });
+    }
+    for (i, (x_i, _)) in points.iter().enumerate() {
+        if points[..i].iter().any(|(x_j, _)| field.sub(x_i, x_j) == field.zero()) {
+            return Err(VsssError::DuplicateShareIndex);
+        }
+    }
+    let max_errors = (points.len() - threshold) / 2;
+
+    // Unknowns: the t + e coefficients of Q, then the e low coefficients of the monic E.
+    // Row i encodes Q(x_i) - y_i * (E(x_i) - x_i^e) = y_i * x_i^e.
+    let q_len = threshold + max_errors;
+    let rows = points.iter().map(|(x, y)| {
+        let mut powers = Vec::with_capacity(q_len + 1);
+        powers.push(field.one());
+        for k in 1..=q_len {
+            powers.push(field.mul(&powers[k - 1], x));
+        }
+        let mut row = powers[..q_len].to_vec();
+        row.extend(powers[..max_errors].iter().map(|power| field.sub(&field.zero(), &field.mul(y, power))));
+        row.push(field.mul(y, &powers[max_errors]));
+        row
+    }).collect();
+    let solution = solve(field, rows, q_len + max_errors)?.ok_or(VsssError::InconsistentShares)?;
+
+    let mut locator = solution[q_len..].to_vec();
+    locator.push(field.one());
+    let (quotient, remainder) = divide_by_monic(field, &solution[..q_len], &locator);
+    if remainder.iter().any(|coef| *coef != field.zero()) {
+        return Err(VsssError::InconsistentShares);
+    }
+
+    let polynomial = Polynomial { coefficients: quotient };
+    let corrupted: Vec<usize> = points.iter().enumerate()
+        .filter(|(_, (x, y))| polynomial.evaluate_over(field, x) != *y)
+        .map(|(position, _)| position)
+        .collect();
+    if corrupted.len() > max_errors {
+        return Err(VsssError::InconsistentShares);
+    }
+    Ok((polynomial, corrupted))
+}
+
+/// Solves a linear system given as augmented rows by Gauss–Jordan elimination.
+///
+/// # Returns
+///
+/// One solution, with the free unknowns set to zero, or `None` if the system has no solution.
+fn solve<F: PrimeField>(field: &F, mut rows: Vec<Vec<F::Element>>, unknowns: usize) -> Result<Option<Vec<F::Element>>> {
+    let mut pivots = Vec::new();
+    for column in 0..unknowns {
+        let rank = pivots.len();
+        let Some(pivot) = (rank..rows.len()).find(|&r| rows[r][column] != field.zero()) else {
+            continue;
+        };
+        rows.swap(rank, pivot);
+        let inverse = field.inv(&rows[rank][column]).ok_or(VsssError::NonInvertible)?;
+        rows[rank] = rows[rank].iter().map(|value| field.mul(value, &inverse)).collect();
+        for r in 0..rows.len() {
+            if r != rank && rows[r][column] != field.zero() {
+                let factor = rows[r][column].clone();
+                rows[r] = rows[r].iter().zip(&rows[rank]).map(|(a, b)| field.sub(a, &field.mul(&factor, b))).collect();
+            }
+        }
+        pivots.push(column);
+    }
+    if rows[pivots.len()..].iter().any(|row| row[unknowns] != field.zero()) {
+        return Ok(None);
+    }
+
+    let mut solution = vec![field.zero(); unknowns];
+    for (rank, &column) in pivots.iter().enumerate() {
+        solution[column] = rows[rank][unknowns].clone();
+    }
+    Ok(Some(solution))
+}
+
+/// Divides `dividend` by the monic `divisor`, both given by their coefficients from the constant
+/// term up, and returns the quotient and the remainder.
+fn divide_by_monic<F: PrimeField>(
+    field: &F,
+    dividend: &[F::Element],
+    divisor: &[F::Element],
+) -> (Vec<F::Element>, Vec<F::Element>) {
+    let degree = divisor.len() - 1;
+    let mut remainder = dividend.to_vec();
+    let mut quotient = vec![field.zero(); dividend.len().saturating_sub(degree)];
+    for k in (0..quotient.len()).rev() {
+        let coef = remainder[k + degree].clone();
+        for (j, divisor_coef) in divisor.iter().enumerate() {
+            remainder[k + j] = field.sub(&remainder[k + j], &field.mul(&coef, divisor_coef));
+        }
+        quotient[k] = coef;
+    }
+    remainder.truncate(degree);
+    (quotient, remainder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra::ModPField;
+    use num_bigint::BigUint;
+
+    #[test]
+    fn test_berlekamp_welch_corrects_errors() {
+        let field = ModPField::new(BigUint::from(7919u32));
+        let coefficients = vec![BigUint::from(1234u32), BigUint::from(166u32), BigUint::from(94u32)];
+        let polynomial = Polynomial { coefficients: coefficients.clone() };
+        let mut points: Vec<(BigUint, BigUint)> = (1..=9u64).map(|i| {
+            let x = field.element(i);
+            let y = polynomial.evaluate_over(&field, &x);
+            (x, y)
+        }).collect();
+
+        let (decoded, corrupted) = berlekamp_welch_over(&field, &points, 3).unwrap();
+        assert_eq!((decoded.coefficients, corrupted), (coefficients.clone(), vec![]));
+        points[1].1 = field.add(&points[1].1, &field.one());
+        points[6].1 = field.zero();
+        points[8].1 = field.element(42);
+        let (decoded, corrupted) = berlekamp_welch_over(&field, &points, 3).unwrap();
+        assert_eq!((decoded.coefficients, corrupted), (coefficients.clone(), vec![1, 6, 8]));
+
+        // Four errors among nine points of a degree-2 polynomial exceed the capacity of three.
+        points[0].1 = field.add(&points[0].1, &field.one());
+        assert!(berlekamp_welch_over(&field, &points, 3).map_or(true, |(decoded, _)| decoded.coefficients != coefficients));
+    }
+
+    #[test]
+    fn test_berlekamp_welch_rejects_invalid_points() {
+        let field = ModPField::new(BigUint::from(7919u32));
+        let points = vec![(field.element(1), field.element(5)), (field.element(1), field.element(6))];
+        assert_eq!(berlekamp_welch_over(&field, &points, 2).err(), Some(VsssError::DuplicateShareIndex));
+        assert_eq!(
+            berlekamp_welch_over(&field, &points[..1], 2).err(),
+            Some(VsssError::InsufficientShares { required: 2, provided: 1 })
+        );
+    }
+}
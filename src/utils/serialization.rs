@@ -0,0 +1,148 @@
+//! Serde support for the public types of the crate, enabled with the `serde` feature.
+//!
+//! Big integers are encoded as big-endian hexadecimal strings, and byte strings such as dealing
+//! identifiers and seeds as plain hexadecimal strings, so the encoding does not depend on the
+//! internal representation of `num-bigint` and stays readable in JSON. The same encoding is used
+//! by binary formats such as CBOR.
+//!
+//! The modules below can be used with `#[serde(with = "...")]` to store the values returned by the
+//! schemes, such as commitment vectors, in application structs:
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Dealing {
+//!     params: FeldmanVSSParams,
+//!     #[serde(with = "vsss_rust::utils::serialization::biguint_vec")]
+//!     commitments: Vec<BigUint>,
+//!     shares: Vec<Share>,
+//! }
+//! ```
+//!
+//! Deserialized parameters are not validated; call `is_valid` or `verify_group_params` on
+//! parameters received from an untrusted source before using them.
+
+use num_bigint::BigUint;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serializer};
+
+/// Encodes a `BigUint` as a big-endian hexadecimal string.
+pub fn encode_biguint(value: &BigUint) -> String {
+    hex::encode(value.to_bytes_be())
+}
+
+/// Decodes a big-endian hexadecimal string into a `BigUint`.
+pub fn decode_biguint(encoded: &str) -> Result<BigUint, hex::FromHexError> {
+    if encoded.is_empty() {
+        return Err(hex::FromHexError::InvalidStringLength);
+    }
+    Ok(BigUint::from_bytes_be(&hex::decode(encoded)?))
+}
+
+/// (De)serializes a `BigUint` as a hexadecimal string.
+pub mod biguint {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &BigUint, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&encode_biguint(value))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BigUint, D::Error> {
+        decode_biguint(&String::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+/// (De)serializes a `Vec<BigUint>` as a list of hexadecimal strings.
+pub mod biguint_vec {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(values: &[BigUint], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(values.iter().map(encode_biguint))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<BigUint>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|encoded| decode_biguint(encoded).map_err(D::Error::custom))
+            .collect()
+    }
+}
+
+/// (De)serializes a `Vec<Vec<BigUint>>` as a list of lists of hexadecimal strings.
+pub mod biguint_vec_vec {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(values: &[Vec<BigUint>], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(values.iter().map(|row| row.iter().map(encode_biguint).collect::<Vec<_>>()))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Vec<BigUint>>, D::Error> {
+        Vec::<Vec<String>>::deserialize(deserializer)?
+            .iter()
+            .map(|row| row.iter().map(|encoded| decode_biguint(encoded).map_err(D::Error::custom)).collect())
+            .collect()
+    }
+}
+
+/// (De)serializes a byte string, such as a `DealingId` or a seed, as a hexadecimal string.
+/// Fixed-size arrays only accept strings of exactly their length.
+pub mod bytes {
+    use super::*;
+
+    pub fn serialize<T: AsRef<[u8]>, S: Serializer>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(bytes))
+    }
+
+    pub fn deserialize<'de, T: TryFrom<Vec<u8>>, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        let decoded = hex::decode(String::deserialize(deserializer)?).map_err(D::Error::custom)?;
+        let len = decoded.len();
+        T::try_from(decoded).map_err(|_| D::Error::custom(format!("unexpected byte string length {}", len)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feldman_verifiability::FeldmanVSSParams;
+    use crate::secret::Secret;
+    use crate::utils::{group_id, Scheme, Share, StandardGroup};
+    use serde::Serialize;
+
+    #[derive(Serialize, Deserialize)]
+    struct Dealing {
+        params: FeldmanVSSParams,
+        #[serde(with = "biguint_vec")]
+        commitments: Vec<BigUint>,
+        shares: Vec<Share>,
+    }
+
+    #[test]
+    fn test_share_encoding() {
+        let modulus = BigUint::from(7919u32);
+        let share = Share::new(BigUint::from(1u32), Secret::new(BigUint::from(0x1234u32)), 2, group_id(&modulus), [0xab; 16], Scheme::Shamir);
+        let json = serde_json::to_string(&share).unwrap();
+        assert!(json.starts_with(r#"{"index":"01","value":"1234","threshold":2,"group_id":""#));
+        assert!(json.ends_with(r#","dealing_id":"abababababababababababababababab","scheme":"Shamir"}"#));
+        assert_eq!(serde_json::from_str::<Share>(&json).unwrap(), share);
+
+        let truncated = json.replace("abababababababababababababababab", "abab");
+        assert!(serde_json::from_str::<Share>(&truncated).is_err());
+        assert!(serde_json::from_str::<Share>(&json.replace(r#""1234""#, r#""12x4""#)).is_err());
+        assert!(decode_biguint("").is_err());
+    }
+
+    #[test]
+    fn test_dealing_round_trip() {
+        let params = FeldmanVSSParams::from_standard_group(StandardGroup::Ffdhe2048);
+        let (shares, commitments) = params.generate_shares(&Secret::new(BigUint::from(42u32)), 2, 3).unwrap();
+        let dealing = Dealing { params, commitments, shares };
+
+        let json = serde_json::to_string(&dealing).unwrap();
+        let decoded: Dealing = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.params.p, dealing.params.p);
+        assert_eq!(decoded.commitments, dealing.commitments);
+        assert_eq!(decoded.shares, dealing.shares);
+        for share in &decoded.shares {
+            assert!(crate::feldman_verifiability::verify_share(share, &decoded.commitments, &decoded.params));
+        }
+    }
+}
@@ -0,0 +1,137 @@
+//! Typed shares for the `BigUint` secret sharing schemes.
+//!
+//! A `Share` records, besides its `(index, value)` point, the threshold of the dealing it belongs
+//! to, a fingerprint of the modulus its value is reduced by, and a random dealing identifier drawn
+//! once per call to `generate_shares`. Reconstruction uses this metadata to refuse shares from
+//! different dealings or moduli instead of silently interpolating garbage.
+//!
+//! The value of a share is a `Secret`, so it is wiped when the share is dropped and redacted when
+//! the share is formatted; the index and the metadata are public.
+
+use super::hash_data;
+use crate::error::{Result, VsssError};
+use crate::secret::Secret;
+use num_bigint::BigUint;
+use rand::{thread_rng, CryptoRng, RngCore};
+
+/// A random identifier shared by all shares produced by one call to `generate_shares`.
+pub type DealingId = [u8; 16];
+
+/// The SHA-256 fingerprint of the modulus share values are reduced by.
+pub type GroupId = [u8; 32];
+
+/// The scheme a share was produced by. Pedersen blinding shares are told apart from the shares
+/// of the secret, so the two halves of a Pedersen dealing are never interpolated together.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Scheme {
+    /// A share of Shamir's Secret Sharing.
+    Shamir = 1,
+    /// A share of Feldman's VSS.
+    Feldman = 2,
+    /// A share of the secret in Pedersen's VSS.
+    Pedersen = 3,
+    /// A share of the blinding polynomial in Pedersen's VSS.
+    PedersenBlinding = 4,
+}
+
+/// A share of a secret, together with the metadata identifying the dealing it belongs to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Share {
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::biguint"))]
+    pub index: BigUint, // x-coordinate, the point the polynomial was evaluated at
+    pub value: Secret, // y-coordinate, the value of the polynomial at the index
+    pub threshold: usize, // Minimum number of shares required to reconstruct the secret
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::bytes"))]
+    pub group_id: GroupId, // Fingerprint of the modulus, see `group_id`
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::bytes"))]
+    pub dealing_id: DealingId, // Identifier of the dealing the share belongs to
+    pub scheme: Scheme, // Scheme the share was produced by
+}
+
+impl Share {
+    /// Creates a share from its point and the metadata of its dealing.
+    pub fn new(index: BigUint, value: Secret, threshold: usize, group_id: GroupId, dealing_id: DealingId, scheme: Scheme) -> Self {
+        Share { index, value, threshold, group_id, dealing_id, scheme }
+    }
+
+    /// Returns the `(index, value)` point of the share. The returned value is a plain copy of
+    /// the secret share value, which is not wiped on drop.
+    pub fn point(&self) -> (BigUint, BigUint) {
+        (self.index.clone(), self.value.expose_secret().clone())
+    }
+}
+
+/// Computes the fingerprint identifying shares reduced modulo `modulus`.
+pub fn group_id(modulus: &BigUint) -> GroupId {
+    let mut data = b"vsss-rust/modulus".to_vec();
+    data.extend_from_slice(&modulus.to_bytes_be());
+    let mut id = GroupId::default();
+    id.copy_from_slice(&hash_data(&data));
+    id
+}
+
+/// Draws a fresh random dealing identifier.
+pub fn new_dealing_id() -> DealingId {
+    new_dealing_id_with_rng(&mut thread_rng())
+}
+
+/// Draws a fresh dealing identifier from `rng`.
+pub fn new_dealing_id_with_rng<R: RngCore + CryptoRng>(rng: &mut R) -> DealingId {
+    let mut id = DealingId::default();
+    rng.fill_bytes(&mut id);
+    id
+}
+
+/// Checks that `shares` can be combined modulo `modulus` and returns their points.
+///
+/// # Returns
+///
+/// The `(index, value)` points. Fails with `VsssError::GroupMismatch` if a share was reduced by a
+/// different modulus, `VsssError::MixedDealings` if the shares disagree on their dealing, scheme or
+/// threshold, and `VsssError::InsufficientShares` if there are fewer shares than the threshold.
+pub fn dealing_points(shares: &[Share], modulus: &BigUint) -> Result<Vec<(BigUint, BigUint)>> {
+    let first = shares.first().ok_or(VsssError::InsufficientShares { required: 1, provided: 0 })?;
+    let group_id = group_id(modulus);
+    if shares.iter().any(|share| share.group_id != group_id) {
+        return Err(VsssError::GroupMismatch);
+    }
+    if shares.iter().any(|share| {
+        share.dealing_id != first.dealing_id || share.threshold != first.threshold || share.scheme != first.scheme
+    }) {
+        return Err(VsssError::MixedDealings);
+    }
+    if shares.len() < first.threshold {
+        return Err(VsssError::InsufficientShares { required: first.threshold, provided: shares.len() });
+    }
+    Ok(shares.iter().map(Share::point).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dealing_points() {
+        let modulus = BigUint::from(7919u32);
+        let dealing_id = new_dealing_id();
+        let shares: Vec<Share> = (1..=3u32).map(|i| {
+            Share::new(BigUint::from(i), BigUint::from(i * 10).into(), 2, group_id(&modulus), dealing_id, Scheme::Shamir)
+        }).collect();
+
+        assert_eq!(dealing_points(&shares[..2], &modulus).unwrap().len(), 2);
+        assert_eq!(
+            dealing_points(&shares[..1], &modulus),
+            Err(VsssError::InsufficientShares { required: 2, provided: 1 })
+        );
+        assert_eq!(dealing_points(&shares, &BigUint::from(7907u32)), Err(VsssError::GroupMismatch));
+
+        let mut mixed = shares.clone();
+        mixed[1].dealing_id = new_dealing_id();
+        assert_eq!(dealing_points(&mixed, &modulus), Err(VsssError::MixedDealings));
+        let mut mixed = shares.clone();
+        mixed[1].scheme = Scheme::PedersenBlinding;
+        assert_eq!(dealing_points(&mixed, &modulus), Err(VsssError::MixedDealings));
+    }
+}
@@ -0,0 +1,379 @@
+//! A compact, versioned binary encoding for shares and commitment sets.
+//!
+//! Every record starts with the magic bytes `VSSS`, a format version and a record type, and ends
+//! with the first four bytes of the SHA-256 hash of everything before them. All integers are
+//! big-endian. A share is encoded as:
+//!
+//! | Field        | Size              | Content                                       |
+//! |--------------|-------------------|-----------------------------------------------|
+//! | magic        | 4                 | `VSSS`                                        |
+//! | version      | 1                 | `1`                                           |
+//! | record type  | 1                 | `1` for a share                               |
+//! | scheme       | 1                 | the `Scheme` discriminant                     |
+//! | threshold    | 4                 | at least `1`                                  |
+//! | index        | 4                 | at least `1`, below the modulus               |
+//! | group id     | 32                | `group_id` of the modulus                     |
+//! | dealing id   | 16                | the dealing identifier                        |
+//! | value length | 2                 | the byte length of the modulus                |
+//! | value        | value length      | the share value, below the modulus            |
+//! | checksum     | 4                 | `SHA-256(everything above)[..4]`              |
+//!
+//! A commitment set is encoded as the magic bytes, the version, record type `2`, the scheme, the
+//! number of commitments (4 bytes), the element length (2 bytes), the `group_id` of the group
+//! modulus `p`, the commitments as fixed-length elements of `[1, p)`, and the checksum.
+//!
+//! Parsing is strict: every field is checked, including the padding of fixed-length values, so each
+//! share or commitment set has exactly one valid encoding.
+
+use super::{group_id, hash_data, DealingId, GroupId, Scheme, Share};
+use crate::error::{Result, VsssError};
+use crate::secret::Secret;
+use num_bigint::BigUint;
+use num_traits::Zero;
+use zeroize::Zeroizing;
+
+/// The magic bytes every record starts with.
+pub const MAGIC: [u8; 4] = *b"VSSS";
+
+/// The current version of the format.
+pub const VERSION: u8 = 1;
+
+/// The record type of a share.
+const RECORD_SHARE: u8 = 1;
+
+/// The record type of a commitment set.
+const RECORD_COMMITMENTS: u8 = 2;
+
+/// The length of the checksum.
+const CHECKSUM_LEN: usize = 4;
+
+impl Scheme {
+    /// Returns the scheme with the given wire identifier.
+    fn from_id(id: u8) -> Result<Self> {
+        match id {
+            1 => Ok(Scheme::Shamir),
+            2 => Ok(Scheme::Feldman),
+            3 => Ok(Scheme::Pedersen),
+            4 => Ok(Scheme::PedersenBlinding),
+            _ => Err(VsssError::InvalidEncoding),
+        }
+    }
+}
+
+impl Share {
+    /// Encodes the share in the binary wire format.
+    ///
+    /// # Arguments
+    ///
+    /// * `modulus` - The modulus the share was generated with, which fixes the length of the value.
+    ///
+    /// # Returns
+    ///
+    /// The encoding, or `VsssError::GroupMismatch` if the share was generated for another modulus,
+    /// `VsssError::InvalidShareIndex` if the index is zero or does not fit in 32 bits, and
+    /// `VsssError::InvalidEncoding` if the threshold does not fit in 32 bits or the value is not
+    /// below the modulus.
+    pub fn to_bytes(&self, modulus: &BigUint) -> Result<Vec<u8>> {
+        if self.group_id != group_id(modulus) {
+            return Err(VsssError::GroupMismatch);
+        }
+        let index = u32::try_from(&self.index).map_err(|_| VsssError::InvalidShareIndex)?;
+        if index == 0 {
+            return Err(VsssError::InvalidShareIndex);
+        }
+        let threshold = u32::try_from(self.threshold).map_err(|_| VsssError::InvalidEncoding)?;
+        if threshold == 0 {
+            return Err(VsssError::InvalidEncoding);
+        }
+
+        let mut bytes = header(RECORD_SHARE, self.scheme);
+        bytes.extend_from_slice(&threshold.to_be_bytes());
+        bytes.extend_from_slice(&index.to_be_bytes());
+        bytes.extend_from_slice(&self.group_id);
+        bytes.extend_from_slice(&self.dealing_id);
+        let len = element_len(modulus)?;
+        bytes.extend_from_slice(&(len as u16).to_be_bytes());
+        push_element(&mut bytes, self.value.expose_secret(), modulus, len)?;
+        Ok(finish(bytes))
+    }
+
+    /// Decodes a share from the binary wire format.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The encoding produced by `to_bytes`.
+    /// * `modulus` - The modulus the share was generated with.
+    ///
+    /// # Returns
+    ///
+    /// The share, `VsssError::GroupMismatch` if it was generated for another modulus, or
+    /// `VsssError::InvalidEncoding` if the bytes are not the canonical encoding of a share, for
+    /// example because of a wrong checksum, an unknown version or a value not below the modulus.
+    pub fn from_bytes(bytes: &[u8], modulus: &BigUint) -> Result<Share> {
+        let mut reader = Reader::new(bytes, RECORD_SHARE)?;
+        let scheme = Scheme::from_id(reader.u8()?)?;
+        let threshold = reader.u32()? as usize;
+        let index = reader.u32()?;
+        let mut ids = [0u8; 48];
+        ids.copy_from_slice(reader.take(48)?);
+        let (group_id_bytes, dealing_id_bytes) = ids.split_at(32);
+        if threshold == 0 || index == 0 {
+            return Err(VsssError::InvalidEncoding);
+        }
+        let share_group_id: GroupId = group_id_bytes.try_into().map_err(|_| VsssError::InvalidEncoding)?;
+        if share_group_id != group_id(modulus) {
+            return Err(VsssError::GroupMismatch);
+        }
+        let dealing_id: DealingId = dealing_id_bytes.try_into().map_err(|_| VsssError::InvalidEncoding)?;
+        let len = element_len(modulus)?;
+        if reader.u16()? as usize != len {
+            return Err(VsssError::InvalidEncoding);
+        }
+        let value = Secret::new(reader.element(len, modulus)?);
+        reader.finish()?;
+
+        let index = BigUint::from(index);
+        if &index >= modulus {
+            return Err(VsssError::InvalidEncoding);
+        }
+        Ok(Share::new(index, value, threshold, share_group_id, dealing_id, scheme))
+    }
+}
+
+/// Encodes a set of commitments in the binary wire format.
+///
+/// # Arguments
+///
+/// * `commitments` - The commitments published by the dealer, elements of `Z_p^*`.
+/// * `scheme` - The scheme the commitments belong to, `Scheme::Feldman` or `Scheme::Pedersen`.
+/// * `p` - The group modulus, which fixes the length of the elements.
+///
+/// # Returns
+///
+/// The encoding, or `VsssError::InvalidEncoding` if a commitment is not in `[1, p)`, the scheme has
+/// no commitments, or there are more than `u32::MAX` commitments.
+pub fn commitments_to_bytes(commitments: &[BigUint], scheme: Scheme, p: &BigUint) -> Result<Vec<u8>> {
+    if !matches!(scheme, Scheme::Feldman | Scheme::Pedersen) {
+        return Err(VsssError::InvalidEncoding);
+    }
+    let count = u32::try_from(commitments.len()).map_err(|_| VsssError::InvalidEncoding)?;
+    let len = element_len(p)?;
+
+    let mut bytes = header(RECORD_COMMITMENTS, scheme);
+    bytes.extend_from_slice(&count.to_be_bytes());
+    bytes.extend_from_slice(&(len as u16).to_be_bytes());
+    bytes.extend_from_slice(&group_id(p));
+    for commitment in commitments {
+        if commitment.is_zero() {
+            return Err(VsssError::InvalidEncoding);
+        }
+        push_element(&mut bytes, commitment, p, len)?;
+    }
+    Ok(finish(bytes))
+}
+
+/// Decodes a set of commitments from the binary wire format.
+///
+/// # Arguments
+///
+/// * `bytes` - The encoding produced by `commitments_to_bytes`.
+/// * `scheme` - The scheme the commitments are expected to belong to.
+/// * `p` - The group modulus.
+///
+/// # Returns
+///
+/// The commitments, `VsssError::GroupMismatch` if they were encoded for another modulus, or
+/// `VsssError::InvalidEncoding` if the bytes are not the canonical encoding of a commitment set of
+/// `scheme`.
+pub fn commitments_from_bytes(bytes: &[u8], scheme: Scheme, p: &BigUint) -> Result<Vec<BigUint>> {
+    let mut reader = Reader::new(bytes, RECORD_COMMITMENTS)?;
+    if Scheme::from_id(reader.u8()?)? != scheme {
+        return Err(VsssError::InvalidEncoding);
+    }
+    let count = reader.u32()? as usize;
+    let len = element_len(p)?;
+    if reader.u16()? as usize != len {
+        return Err(VsssError::InvalidEncoding);
+    }
+    if reader.take(32)? != group_id(p) {
+        return Err(VsssError::GroupMismatch);
+    }
+    if reader.remaining() != count.saturating_mul(len) {
+        return Err(VsssError::InvalidEncoding);
+    }
+    let commitments = (0..count).map(|_| {
+        let commitment = reader.element(len, p)?;
+        if commitment.is_zero() {
+            return Err(VsssError::InvalidEncoding);
+        }
+        Ok(commitment)
+    }).collect::<Result<Vec<_>>>()?;
+    reader.finish()?;
+    Ok(commitments)
+}
+
+/// Returns the byte length of elements reduced modulo `modulus`.
+fn element_len(modulus: &BigUint) -> Result<usize> {
+    let len = modulus.bits().div_ceil(8) as usize;
+    if len == 0 || len > u16::MAX as usize {
+        return Err(VsssError::InvalidEncoding);
+    }
+    Ok(len)
+}
+
+/// Starts a record with the magic bytes, version, record type and scheme.
+fn header(record: u8, scheme: Scheme) -> Vec<u8> {
+    let mut bytes = MAGIC.to_vec();
+    bytes.extend_from_slice(&[VERSION, record, scheme as u8]);
+    bytes
+}
+
+/// Appends `value`, which must be below `modulus`, as a `len`-byte big-endian integer.
+fn push_element(bytes: &mut Vec<u8>, value: &BigUint, modulus: &BigUint, len: usize) -> Result<()> {
+    if value >= modulus {
+        return Err(VsssError::InvalidEncoding);
+    }
+    let digits = Zeroizing::new(value.to_bytes_be());
+    bytes.resize(bytes.len() + len - digits.len(), 0);
+    bytes.extend_from_slice(&digits);
+    Ok(())
+}
+
+/// Appends the checksum to a record.
+fn finish(mut bytes: Vec<u8>) -> Vec<u8> {
+    let checksum = hash_data(&bytes);
+    bytes.extend_from_slice(&checksum[..CHECKSUM_LEN]);
+    bytes
+}
+
+/// Reads the fields of a record after checking its header and checksum.
+struct Reader<'a> {
+    bytes: &'a [u8], // The record without its checksum
+    position: usize, // Offset of the next field
+}
+
+impl<'a> Reader<'a> {
+    /// Checks the magic bytes, version, record type and checksum of `bytes`.
+    fn new(bytes: &'a [u8], record: u8) -> Result<Self> {
+        if bytes.len() < MAGIC.len() + 2 + CHECKSUM_LEN {
+            return Err(VsssError::InvalidEncoding);
+        }
+        let (body, checksum) = bytes.split_at(bytes.len() - CHECKSUM_LEN);
+        if body[..MAGIC.len()] != MAGIC || body[MAGIC.len()] != VERSION || body[MAGIC.len() + 1] != record {
+            return Err(VsssError::InvalidEncoding);
+        }
+        if hash_data(body)[..CHECKSUM_LEN] != *checksum {
+            return Err(VsssError::InvalidEncoding);
+        }
+        Ok(Reader { bytes: body, position: MAGIC.len() + 2 })
+    }
+
+    fn remaining(&self) -> usize {
+        self.bytes.len() - self.position
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.remaining() < len {
+            return Err(VsssError::InvalidEncoding);
+        }
+        let field = &self.bytes[self.position..self.position + len];
+        self.position += len;
+        Ok(field)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_be_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    /// Reads a `len`-byte big-endian integer, which must be below `modulus`.
+    fn element(&mut self, len: usize, modulus: &BigUint) -> Result<BigUint> {
+        let value = BigUint::from_bytes_be(self.take(len)?);
+        if &value >= modulus {
+            return Err(VsssError::InvalidEncoding);
+        }
+        Ok(value)
+    }
+
+    /// Checks that every field has been read.
+    fn finish(self) -> Result<()> {
+        if self.remaining() != 0 {
+            return Err(VsssError::InvalidEncoding);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feldman_verifiability::FeldmanVSSParams;
+    use crate::utils::StandardGroup;
+
+    #[test]
+    fn test_share_round_trip() {
+        let modulus = BigUint::from(65537u32);
+        let share = Share::new(BigUint::from(3u32), Secret::new(BigUint::from(0x1234u32)), 2, group_id(&modulus), [7; 16], Scheme::Shamir);
+        let bytes = share.to_bytes(&modulus).unwrap();
+        assert_eq!(bytes.len(), 4 + 3 + 4 + 4 + 32 + 16 + 2 + 3 + 4);
+        assert_eq!(&bytes[..7], b"VSSS\x01\x01\x01");
+        assert_eq!(&bytes[bytes.len() - 7..bytes.len() - 4], &[0x00, 0x12, 0x34]);
+        assert_eq!(Share::from_bytes(&bytes, &modulus).unwrap(), share);
+
+        assert_eq!(Share::from_bytes(&bytes, &BigUint::from(7919u32)), Err(VsssError::GroupMismatch));
+        assert_eq!(share.to_bytes(&BigUint::from(7919u32)), Err(VsssError::GroupMismatch));
+        assert_eq!(Share::from_bytes(&bytes[..bytes.len() - 1], &modulus), Err(VsssError::InvalidEncoding));
+        let mut extended = bytes.clone();
+        extended.push(0);
+        assert_eq!(Share::from_bytes(&extended, &modulus), Err(VsssError::InvalidEncoding));
+    }
+
+    #[test]
+    fn test_rejects_non_canonical_shares() {
+        let modulus = BigUint::from(65537u32);
+        let share = Share::new(BigUint::from(3u32), Secret::new(BigUint::from(5u32)), 2, group_id(&modulus), [7; 16], Scheme::Feldman);
+        let bytes = share.to_bytes(&modulus).unwrap();
+        // Re-signs a modified body so that only the field under test is wrong.
+        let resign = |mutate: &dyn Fn(&mut Vec<u8>)| {
+            let mut body = bytes[..bytes.len() - CHECKSUM_LEN].to_vec();
+            mutate(&mut body);
+            finish(body)
+        };
+
+        let mut flipped = bytes.clone();
+        flipped[20] ^= 1;
+        assert_eq!(Share::from_bytes(&flipped, &modulus), Err(VsssError::InvalidEncoding));
+        for mutate in [
+            &(|b: &mut Vec<u8>| b[4] = 2) as &dyn Fn(&mut Vec<u8>),
+            &|b: &mut Vec<u8>| b[6] = 9,
+            &|b: &mut Vec<u8>| b[7..11].copy_from_slice(&[0; 4]),
+            &|b: &mut Vec<u8>| b[11..15].copy_from_slice(&[0; 4]),
+            &|b: &mut Vec<u8>| { let n = b.len(); b[n - 3..].copy_from_slice(&[0x01, 0x00, 0x01]) },
+            &|b: &mut Vec<u8>| { let n = b.len(); b[n - 5..n - 3].copy_from_slice(&[0x00, 0x04]); b.insert(n - 3, 0) },
+        ] {
+            assert_eq!(Share::from_bytes(&resign(mutate), &modulus), Err(VsssError::InvalidEncoding));
+        }
+        assert_eq!(Share::from_bytes(&resign(&|_| ()), &modulus), Ok(share));
+    }
+
+    #[test]
+    fn test_commitments_round_trip() {
+        let params = FeldmanVSSParams::from_standard_group(StandardGroup::Ffdhe2048);
+        let (_, commitments) = params.generate_shares(&Secret::new(BigUint::from(42u32)), 3, 5).unwrap();
+        let bytes = commitments_to_bytes(&commitments, Scheme::Feldman, &params.p).unwrap();
+        assert_eq!(bytes.len(), 4 + 3 + 4 + 2 + 32 + 3 * 256 + 4);
+        assert_eq!(commitments_from_bytes(&bytes, Scheme::Feldman, &params.p).unwrap(), commitments);
+
+        assert_eq!(commitments_from_bytes(&bytes, Scheme::Pedersen, &params.p), Err(VsssError::InvalidEncoding));
+        assert_eq!(commitments_from_bytes(&bytes, Scheme::Feldman, &params.q), Err(VsssError::GroupMismatch));
+        assert_eq!(commitments_to_bytes(&commitments, Scheme::Shamir, &params.p), Err(VsssError::InvalidEncoding));
+        assert_eq!(commitments_to_bytes(&[BigUint::zero()], Scheme::Feldman, &params.p), Err(VsssError::InvalidEncoding));
+    }
+}
@@ -0,0 +1,88 @@
+//! End-to-end tests of the `vsss` binary and its exit statuses.
+#![cfg(feature = "cli")]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+/// Runs `vsss` with `args`.
+fn vsss(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_vsss")).args(args).output().expect("the vsss binary runs")
+}
+
+/// Creates an empty scratch directory for one test.
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("vsss-cli-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn path(path: &Path) -> &str {
+    path.to_str().unwrap()
+}
+
+#[test]
+fn test_split_verify_combine() {
+    let dir = scratch_dir("feldman");
+    let (shares, others) = (dir.join("shares"), dir.join("others"));
+    let (commitments, other_commitments) = (dir.join("commitments.txt"), dir.join("other-commitments.txt"));
+    fs::create_dir_all(&shares).unwrap();
+    fs::create_dir_all(&others).unwrap();
+
+    let split = |out_dir: &Path, commitments: &Path| {
+        vsss(&["split", "-t", "2", "-n", "3", "--scheme", "feldman", "--hex", "00ff", "-c", path(commitments), "-o", path(out_dir)])
+    };
+    assert_eq!(split(&shares, &commitments).status.code(), Some(0));
+    assert_eq!(split(&others, &other_commitments).status.code(), Some(0));
+    let share = |dir: &Path, index: u32| dir.join(format!("share-{}.txt", index));
+
+    let verify = vsss(&["verify", "-c", path(&commitments), path(&share(&shares, 1)), path(&share(&shares, 2))]);
+    assert_eq!(verify.status.code(), Some(0));
+
+    // A well-formed share of another dealing fails verification, a mistyped one cannot be read.
+    let verify = vsss(&["verify", "-c", path(&commitments), path(&share(&shares, 1)), path(&share(&others, 2))]);
+    assert_eq!(verify.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&verify.stdout).contains("share 2: INVALID"));
+    let mut text = fs::read_to_string(share(&shares, 3)).unwrap();
+    let typo = text.len() / 2;
+    let replacement = if &text[typo..typo + 1] == "q" { "p" } else { "q" };
+    text.replace_range(typo..typo + 1, replacement);
+    fs::write(dir.join("typo.txt"), text).unwrap();
+    assert_eq!(vsss(&["verify", "-c", path(&commitments), path(&dir.join("typo.txt"))]).status.code(), Some(3));
+
+    let combine = vsss(&["combine", "--hex", path(&share(&shares, 1)), path(&share(&shares, 3))]);
+    assert_eq!(combine.status.code(), Some(0));
+    assert_eq!(String::from_utf8_lossy(&combine.stdout), "00ff\n");
+    assert_eq!(vsss(&["combine", "--hex", path(&share(&shares, 1))]).status.code(), Some(4));
+
+    // Shares and secrets are private to their owner, and existing files are never overwritten.
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        assert_eq!(fs::metadata(share(&shares, 1)).unwrap().permissions().mode() & 0o777, 0o600);
+    }
+    let secret = dir.join("secret.bin");
+    let combine = |output: &Path| vsss(&["combine", "-o", path(output), path(&share(&shares, 1)), path(&share(&shares, 2))]);
+    assert_eq!(combine(&secret).status.code(), Some(0));
+    assert_eq!(fs::read(&secret).unwrap(), [0x00, 0xff]);
+    assert_eq!(combine(&secret).status.code(), Some(3));
+    assert_eq!(split(&shares, &dir.join("fresh-commitments.txt")).status.code(), Some(3));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_encoding_of_large_groups() {
+    let split = vsss(&["split", "-t", "2", "-n", "3", "-g", "ffdhe6144", "--hex", "00ff"]);
+    assert_eq!(split.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&split.stdout).starts_with("-----BEGIN VSSS SHARE-----"));
+    assert_eq!(vsss(&["split", "-t", "2", "-n", "3", "-g", "ffdhe6144", "--hex", "00ff", "-e", "bech32"]).status.code(), Some(2));
+
+    let dir = scratch_dir("unwritable");
+    let commitments = dir.join("missing").join("commitments.txt");
+    let split = vsss(&["split", "-t", "2", "-n", "3", "--scheme", "feldman", "--hex", "00ff", "-c", path(&commitments)]);
+    assert_eq!(split.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&split.stderr).contains("cannot write"));
+    fs::remove_dir_all(&dir).unwrap();
+}